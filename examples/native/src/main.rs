@@ -47,17 +47,19 @@ impl TimeInstant for StdInstant {
     fn checked_sub(self, duration: StdDuration) -> Option<Self> {
         self.0.checked_sub(duration.0).map(StdInstant)
     }
+
+    fn checked_duration_since(&self, earlier: Self) -> Option<StdDuration> {
+        self.0.checked_duration_since(earlier.0).map(StdDuration)
+    }
 }
 
 // --- Config ---
 
-static CONFIG: Config<StdDuration> = Config {
-    active_low: false,
-    click_timeout: StdDuration(Duration::from_millis(120)),
-    hold_delay: StdDuration(Duration::from_millis(500)),
-    hold_interval: StdDuration(Duration::from_millis(300)),
-    max_click_count: None,
-};
+static CONFIG: Config<StdDuration> = Config::new(
+    StdDuration(Duration::from_millis(120)),
+    StdDuration(Duration::from_millis(500)),
+    StdDuration(Duration::from_millis(300)),
+);
 
 // --- Main ---
 
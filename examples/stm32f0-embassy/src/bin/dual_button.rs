@@ -68,6 +68,12 @@ impl TimeInstant for EmbassyInstant {
     fn checked_sub(self, duration: EmbassyDuration) -> Option<Self> {
         self.0.checked_sub(duration.0).map(EmbassyInstant)
     }
+
+    fn checked_duration_since(&self, earlier: Self) -> Option<EmbassyDuration> {
+        self.0
+            .checked_duration_since(earlier.0)
+            .map(EmbassyDuration)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -117,10 +123,12 @@ const DEBOUNCE_B: Duration = Duration::from_millis(10);
 
 static BUTTON_CONFIG: Config<EmbassyDuration> = Config {
     active_low: true,
-    click_timeout: EmbassyDuration(Duration::from_millis(120)),
-    hold_delay: EmbassyDuration(Duration::from_millis(500)),
-    hold_interval: EmbassyDuration(Duration::from_millis(300)),
     max_click_count: Some(1),
+    ..Config::new(
+        EmbassyDuration(Duration::from_millis(120)),
+        EmbassyDuration(Duration::from_millis(500)),
+        EmbassyDuration(Duration::from_millis(300)),
+    )
 };
 
 // ---------------------------------------------------------------------------
@@ -174,7 +182,7 @@ async fn button_a_task(mut button: ExtiInput<'static>) {
                     sender.send(InputEvent::ButtonAHold).await;
                 }
             }
-            None => {}
+            _ => {}
         }
 
         match result.next_service {
@@ -223,7 +231,7 @@ async fn button_b_task(mut button: ExtiInput<'static>) {
                     sender.send(InputEvent::ButtonBHold).await;
                 }
             }
-            None => {}
+            _ => {}
         }
 
         // After an edge wakes the task, wait for the pin to settle before the
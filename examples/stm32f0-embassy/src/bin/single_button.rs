@@ -55,16 +55,23 @@ impl TimeInstant for EmbassyInstant {
     fn checked_sub(self, duration: EmbassyDuration) -> Option<Self> {
         self.0.checked_sub(duration.0).map(EmbassyInstant)
     }
+
+    fn checked_duration_since(&self, earlier: Self) -> Option<EmbassyDuration> {
+        self.0
+            .checked_duration_since(earlier.0)
+            .map(EmbassyDuration)
+    }
 }
 
 // --- Config ---
 
 static CONFIG: Config<EmbassyDuration> = Config {
     active_low: true,
-    click_timeout: EmbassyDuration(Duration::from_millis(120)),
-    hold_delay: EmbassyDuration(Duration::from_millis(500)),
-    hold_interval: EmbassyDuration(Duration::from_millis(300)),
-    max_click_count: None,
+    ..Config::new(
+        EmbassyDuration(Duration::from_millis(120)),
+        EmbassyDuration(Duration::from_millis(500)),
+        EmbassyDuration(Duration::from_millis(300)),
+    )
 };
 
 // --- Main ---
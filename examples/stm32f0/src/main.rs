@@ -71,13 +71,8 @@ impl TimeInstant for HalInstant {
 
 // --- Config ---
 
-static CONFIG: Config<HalDuration> = Config {
-    active_low: false,
-    click_timeout: HalDuration(120),
-    hold_delay: HalDuration(500),
-    hold_interval: HalDuration(300),
-    max_click_count: None,
-};
+static CONFIG: Config<HalDuration> =
+    Config::new(HalDuration(120), HalDuration(500), HalDuration(300));
 
 // --- Entry ---
 
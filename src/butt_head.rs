@@ -1,13 +1,16 @@
-use crate::TimeDuration;
-use crate::config::Config;
+use crate::config::{Config, TimeFaultPolicy};
 use crate::event::Event;
+use crate::handler::{ButtonHandler, HandlerContext};
 use crate::service_timing::ServiceTiming;
-use crate::state_machine::{Edge, StateMachine};
+use crate::snapshot::{self, RestoreError, SNAPSHOT_LEN};
+use crate::state_machine::{Edge, State, StateMachine};
 use crate::time::TimeInstant;
+use crate::{ButtonState, HoldProgress, TimeDuration};
 
 /// The result of a single `update()` call.
 #[derive(Debug, Clone, Copy)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UpdateResult<D: TimeDuration, I: TimeInstant<Duration = D>> {
     /// The event produced by this update, if any.
     pub event: Option<Event<D, I>>,
@@ -15,12 +18,36 @@ pub struct UpdateResult<D: TimeDuration, I: TimeInstant<Duration = D>> {
     pub next_service: ServiceTiming<D>,
 }
 
+/// Error returned by [`ButtHead::try_update`] under
+/// [`TimeFaultPolicy::Error`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum UpdateError {
+    /// `now` is earlier than the `now` of the previous update.
+    TimeWentBackwards,
+
+    /// A hold deadline cannot be represented by the instant type.
+    DeadlineOverflow,
+}
+
 /// Button input processor.
 ///
 /// Expects clean, debounced input. If your button is subject to mechanical
 /// bounce, debounce the signal before passing it to `update()`.
+///
+/// # Presses that end without `Release`
+///
+/// [`ButtHead::cancel_gesture`], [`ButtHead::suppress_until_release`],
+/// [`ButtHead::disable`] and [`ButtHead::reset`], and a clock fault under
+/// [`TimeFaultPolicy::Reset`], end a press without any `Release`. Anything
+/// the press started on `Press` — a held HID key, a MIDI note, a motor — must
+/// then be stopped by the caller, e.g. with `HidKeyboard::release_button` or
+/// `MidiConfig::release_message`.
 pub struct ButtHead<I: TimeInstant> {
     prev_input: bool,
+    /// `now` of the last successful update, for detecting a rewinding clock.
+    last_now: Option<I>,
+    enabled: bool,
     state_machine: StateMachine<I>,
     config: &'static Config<I::Duration>,
 }
@@ -30,12 +57,83 @@ impl<I: TimeInstant> ButtHead<I> {
     pub fn new(config: &'static Config<I::Duration>) -> Self {
         Self {
             prev_input: false,
+            last_now: None,
+            enabled: true,
             state_machine: StateMachine::new(config),
             config,
         }
     }
 
-    /// Returns `true` if the button is currently physically pressed.
+    /// Creates a `ButtHead` for a device that was woken from a low-power mode
+    /// by the button's press interrupt.
+    ///
+    /// The first `update()` typically runs well after the press — possibly
+    /// after the button has been released again. `wake_at` is the timestamp of
+    /// the wake-up edge (e.g. captured in the EXTI handler); the press is
+    /// recorded at that instant, and the first `update()` returns
+    /// `Event::Press { at: wake_at }` with `ServiceTiming::Immediate`. A short
+    /// press that ended during the wake latency then resolves to a regular
+    /// `Release` and `Click`.
+    pub fn resume_from_wake(config: &'static Config<I::Duration>, wake_at: I) -> Self {
+        Self {
+            prev_input: true,
+            last_now: Some(wake_at),
+            enabled: true,
+            state_machine: StateMachine::pressed_since(config, wake_at),
+            config,
+        }
+    }
+
+    /// Restores a `ButtHead` from a snapshot taken with [`ButtHead::snapshot`].
+    ///
+    /// `elapsed` is the time that passed between taking the snapshot and `now`
+    /// (e.g. the deep-sleep duration), so that a double-click spanning a sleep
+    /// cycle still resolves against the correct `click_timeout`. If the
+    /// restored press or release instant would precede the start of the clock,
+    /// it is clamped to `now`.
+    ///
+    /// Returns an error if the snapshot has an unknown version, a bad checksum,
+    /// or decodes to an impossible state.
+    pub fn restore(
+        config: &'static Config<I::Duration>,
+        snapshot: &[u8; SNAPSHOT_LEN],
+        now: I,
+        elapsed: I::Duration,
+    ) -> Result<Self, RestoreError> {
+        let (state, prev_input, enabled) = snapshot::decode(snapshot, now, elapsed)?;
+        Ok(Self {
+            prev_input,
+            last_now: Some(now),
+            enabled,
+            state_machine: StateMachine::with_state(config, state),
+            config,
+        })
+    }
+
+    /// Encodes the current state into a compact, versioned byte snapshot, e.g.
+    /// to survive a deep sleep that loses RAM. Instants are stored relative to
+    /// `now`. Restore it with [`ButtHead::restore`].
+    ///
+    /// Take the snapshot after an `update()` whose `next_service` is not
+    /// `ServiceTiming::Immediate`; an event still queued for the immediate
+    /// follow-up call is not part of the snapshot.
+    pub fn snapshot(&self, now: I) -> [u8; SNAPSHOT_LEN] {
+        snapshot::encode(
+            &self.state_machine.raw_state(),
+            self.prev_input,
+            self.enabled,
+            now,
+        )
+    }
+
+    /// Returns the configuration this instance was created with.
+    pub fn config(&self) -> &'static Config<I::Duration> {
+        self.config
+    }
+
+    /// Returns `true` if the button is currently physically pressed, as of the
+    /// last `update()`. The level is still tracked while the button is
+    /// disabled.
     pub fn is_pressed(&self) -> bool {
         self.prev_input
     }
@@ -54,6 +152,55 @@ impl<I: TimeInstant> ButtHead<I> {
             .map(|at| now.duration_since(at))
     }
 
+    /// Returns the current gesture state, e.g. to show "waiting for second
+    /// click…" feedback.
+    pub fn state(&self) -> ButtonState<I> {
+        self.state_machine.state()
+    }
+
+    /// Returns the number of clicks accumulated in the current gesture that
+    /// have not yet been reported by a `Click` or `Hold` event.
+    pub fn pending_click_count(&self) -> u8 {
+        match self.state_machine.state() {
+            ButtonState::Pressed {
+                clicks,
+                hold_level: 0,
+                ..
+            } => clicks,
+            ButtonState::WaitForMultiClick { clicks, .. }
+            | ButtonState::PendingPress { clicks, .. } => clicks,
+            _ => 0,
+        }
+    }
+
+    /// Returns how long until the next timed event (`Hold`, `Click`, ...) could
+    /// fire if the input does not change, or `None` if nothing is scheduled.
+    pub fn time_until_next_event(&self, now: I) -> Option<I::Duration> {
+        self.state_machine.time_until_next_event(now)
+    }
+
+    /// Returns how far the current press has progressed toward the next `Hold`
+    /// or `HoldStage` — or toward `Confirmed` when `confirm_duration` is set —
+    /// e.g. to drive a progress ring during "hold to confirm".
+    /// Returns `None` if the button is not pressed, the press has not yet
+    /// lasted `min_press_duration`, or no further hold will fire.
+    pub fn hold_progress(&self, now: I) -> Option<HoldProgress<I::Duration>> {
+        self.state_machine.hold_progress(now)
+    }
+
+    /// Returns how long until the next `Hold` or `HoldStage` fires if the
+    /// button stays pressed, or `None` if none will. Unlike
+    /// [`ButtHead::time_until_next_event`], this ignores `Stuck`.
+    pub fn time_to_next_hold(&self, now: I) -> Option<I::Duration> {
+        self.hold_progress(now).map(|progress| progress.remaining())
+    }
+
+    /// Returns `true` if the button has been pressed for longer than
+    /// `max_press_duration` and is quarantined until it is released.
+    pub fn is_stuck(&self) -> bool {
+        self.state_machine.is_stuck()
+    }
+
     /// Cancels the pending `Click` event when the state machine is in
     /// `WaitForMultiClick`. Returns `true` if cancelled, `false` if the state
     /// machine was not waiting for a click (nothing to cancel).
@@ -66,30 +213,245 @@ impl<I: TimeInstant> ButtHead<I> {
         self.state_machine.cancel_pending_click()
     }
 
+    /// Cancels the gesture in progress, e.g. when the button acted as a
+    /// modifier for another input or the UI context changed. Returns `true` if
+    /// anything was cancelled.
+    ///
+    /// | State | Effect |
+    /// | ----- | ------ |
+    /// | `PendingPress`, `Pressed`, `Confirming` | Same as [`ButtHead::suppress_until_release`] |
+    /// | `WaitForMultiClick` | Same as [`ButtHead::cancel_pending_click`] |
+    /// | `Idle`, `Stuck`, `Suppressed`, `Confirmed`, `Cooldown` | Nothing; returns `false` |
+    ///
+    /// An event already queued for the next call (see
+    /// `ServiceTiming::Immediate`) is still delivered. A cancelled press never
+    /// emits `Release` (see [presses that end without
+    /// `Release`](ButtHead#presses-that-end-without-release)).
+    pub fn cancel_gesture(&mut self) -> bool {
+        self.state_machine.cancel_gesture()
+    }
+
+    /// Ignores the rest of the current press. No further `Hold` or `Stuck`
+    /// fires, and the release emits neither `Release` nor `Click`; clicks that
+    /// preceded the press are dropped as well. The button is back to `Idle`
+    /// once released. A press still shorter than `min_press_duration` never
+    /// emits `Press` (nor `Glitch`), and a hold-to-confirm press emits neither
+    /// `Confirmed` nor `ConfirmAborted`.
+    ///
+    /// Returns `true` if a press was suppressed, `false` if the button was not
+    /// in `ButtonState::PendingPress`, `Pressed` or `Confirming`.
+    pub fn suppress_until_release(&mut self) -> bool {
+        self.state_machine.suppress_until_release()
+    }
+
+    /// Stops processing input, e.g. during an OTA update or while a modal
+    /// screen owns the input. The current gesture and any queued event are
+    /// discarded without emitting events, and `update()` returns no events
+    /// with `ServiceTiming::Idle` until [`ButtHead::enable`] is called.
+    pub fn disable(&mut self) {
+        self.state_machine = StateMachine::new(self.config);
+        self.enabled = false;
+    }
+
+    /// Resumes processing input after [`ButtHead::disable`].
+    ///
+    /// `is_pressed` is the raw pin state (before active-low inversion) at
+    /// `now`; it resynchronizes the input level so that no phantom `Release`
+    /// or `Click` is produced. If the button is held down, it is either adopted
+    /// as a press starting at `now` (the next `update()` emits `Press`) or
+    /// ignored until released, depending on `Config::require_release_on_enable`.
+    ///
+    /// Calling this on an enabled button restarts it the same way.
+    pub fn enable(&mut self, is_pressed: bool, now: I) {
+        let input = if self.config.active_low {
+            !is_pressed
+        } else {
+            is_pressed
+        };
+        self.state_machine = match (input, self.config.require_release_on_enable) {
+            (false, _) => StateMachine::new(self.config),
+            (true, false) => StateMachine::pressed_since(self.config, now),
+            (true, true) => {
+                StateMachine::with_state(self.config, State::Suppressed { pressed_at: now })
+            }
+        };
+        self.prev_input = input;
+        self.last_now = Some(now);
+        self.enabled = true;
+    }
+
+    /// Returns `false` between [`ButtHead::disable`] and [`ButtHead::enable`].
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Abandons the current gesture and any queued event without emitting
+    /// events. If the button is held down, the press is ignored until it is
+    /// released.
+    pub fn reset(&mut self) {
+        let state = match self.state_machine.pressed_at() {
+            Some(pressed_at) if self.prev_input => State::Suppressed { pressed_at },
+            _ => State::Idle,
+        };
+        self.state_machine = StateMachine::with_state(self.config, state);
+    }
+
     /// Advances the state machine.
     ///
     /// `is_pressed` is the raw pin state (before active-low inversion).
     /// `now` is the current timestamp. Returns the resulting event and the
     /// recommended time for the next call.
+    ///
+    /// Clock faults are handled according to `Config::time_fault_policy`, with
+    /// [`TimeFaultPolicy::Error`] treated as [`TimeFaultPolicy::Clamp`]. Use
+    /// [`ButtHead::try_update`] to observe them.
     pub fn update(&mut self, is_pressed: bool, now: I) -> UpdateResult<I::Duration, I> {
+        let policy = match self.config.time_fault_policy {
+            TimeFaultPolicy::Error => TimeFaultPolicy::Clamp,
+            policy => policy,
+        };
+        match self.update_inner(is_pressed, now, policy) {
+            Ok(result) => result,
+            // Clamp and Reset always recover.
+            Err(_) => UpdateResult {
+                event: None,
+                next_service: ServiceTiming::Immediate,
+            },
+        }
+    }
+
+    /// Advances the state machine like [`ButtHead::update`], but reports clock
+    /// faults as an [`UpdateError`] when `Config::time_fault_policy` is
+    /// [`TimeFaultPolicy::Error`].
+    ///
+    /// On error nothing changes, not even the debounced input level, so the
+    /// same input can be retried with a valid `now`. Under the other policies
+    /// this never fails.
+    pub fn try_update(
+        &mut self,
+        is_pressed: bool,
+        now: I,
+    ) -> Result<UpdateResult<I::Duration, I>, UpdateError> {
+        self.update_inner(is_pressed, now, self.config.time_fault_policy)
+    }
+
+    fn update_inner(
+        &mut self,
+        is_pressed: bool,
+        now: I,
+        policy: TimeFaultPolicy,
+    ) -> Result<UpdateResult<I::Duration, I>, UpdateError> {
         let input = if self.config.active_low {
             !is_pressed
         } else {
             is_pressed
         };
 
-        let edge = if input != self.prev_input {
+        if !self.enabled {
             self.prev_input = input;
+            return Ok(UpdateResult {
+                event: None,
+                next_service: ServiceTiming::Idle,
+            });
+        }
+
+        let now = match self.last_now {
+            Some(last) if now.checked_duration_since(last).is_none() => match policy {
+                TimeFaultPolicy::Clamp => last,
+                TimeFaultPolicy::Reset => return Ok(self.reset_gesture(input, now)),
+                TimeFaultPolicy::Error => return Err(UpdateError::TimeWentBackwards),
+            },
+            _ => now,
+        };
+
+        let edge = if input != self.prev_input {
             Some(if input { Edge::Press } else { Edge::Release })
         } else {
             None
         };
 
-        let (event, next_service) = self.state_machine.update(edge, now);
+        match self.state_machine.update(edge, now, policy) {
+            Ok((event, next_service)) => {
+                self.prev_input = input;
+                self.last_now = Some(now);
+                Ok(UpdateResult {
+                    event,
+                    next_service,
+                })
+            }
+            Err(_) if policy == TimeFaultPolicy::Reset => Ok(self.reset_gesture(input, now)),
+            Err(error) => Err(error),
+        }
+    }
 
+    /// Abandons the current gesture after a clock fault. A press in progress
+    /// is ignored until it is released.
+    fn reset_gesture(&mut self, input: bool, now: I) -> UpdateResult<I::Duration, I> {
+        let state = if input {
+            State::Suppressed { pressed_at: now }
+        } else {
+            State::Idle
+        };
+        self.state_machine = StateMachine::with_state(self.config, state);
+        self.prev_input = input;
+        self.last_now = Some(now);
         UpdateResult {
-            event,
-            next_service,
+            event: None,
+            next_service: ServiceTiming::Idle,
+        }
+    }
+
+    /// Advances the state machine like [`ButtHead::update`], dispatching the
+    /// resulting event to `handler` instead of returning it.
+    ///
+    /// Returns the recommended time for the next call.
+    pub fn update_with<H: ButtonHandler<I>>(
+        &mut self,
+        handler: &mut H,
+        is_pressed: bool,
+        now: I,
+    ) -> ServiceTiming<I::Duration> {
+        let result = self.update(is_pressed, now);
+        if let Some(event) = result.event {
+            self.dispatch(handler, event);
+        }
+        result.next_service
+    }
+
+    /// Calls the `handler` method matching `event`.
+    pub(crate) fn dispatch<H: ButtonHandler<I>>(
+        &mut self,
+        handler: &mut H,
+        event: Event<I::Duration, I>,
+    ) {
+        let mut ctx = HandlerContext::new(&mut self.state_machine);
+        match event {
+            Event::Press { at } => handler.on_press(&mut ctx, at),
+            Event::Release {
+                duration,
+                click_follows,
+            } => handler.on_release(&mut ctx, duration, click_follows),
+            Event::Click { count } => handler.on_click(&mut ctx, count),
+            Event::Hold {
+                clicks_before,
+                level,
+            } => handler.on_hold(&mut ctx, clicks_before, level),
+            Event::HoldEnd {
+                clicks_before,
+                levels,
+                duration,
+            } => handler.on_hold_end(&mut ctx, clicks_before, levels, duration),
+            Event::Stuck { duration } => handler.on_stuck(&mut ctx, duration),
+            Event::Glitch { duration } => handler.on_glitch(&mut ctx, duration),
+            Event::HoldStage {
+                index,
+                clicks_before,
+            } => handler.on_hold_stage(&mut ctx, index, clicks_before),
+            Event::ConfirmStarted { at } => handler.on_confirm_started(&mut ctx, at),
+            Event::ConfirmProgress { elapsed } => handler.on_confirm_progress(&mut ctx, elapsed),
+            Event::Confirmed => handler.on_confirmed(&mut ctx),
+            Event::ConfirmAborted { held_for } => handler.on_confirm_aborted(&mut ctx, held_for),
+            Event::ChannelFault { no, nc } => handler.on_channel_fault(&mut ctx, no, nc),
         }
     }
 }
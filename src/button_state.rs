@@ -0,0 +1,47 @@
+use crate::TimeInstant;
+
+/// A read-only view of the gesture state machine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ButtonState<I: TimeInstant> {
+    /// No gesture in progress.
+    Idle,
+
+    /// The button was pressed at `since` but has not yet been held for
+    /// `min_press_duration`; no `Press` has been emitted. `clicks` is the
+    /// number of clicks that preceded this press.
+    PendingPress { since: I, clicks: u8 },
+
+    /// The button is held down. `since` is the press instant, `clicks` the
+    /// number of clicks that preceded this press, and `hold_level` the number
+    /// of `Hold` events emitted so far during this press.
+    Pressed {
+        since: I,
+        clicks: u8,
+        hold_level: u32,
+    },
+
+    /// The button was released after `clicks` clicks and a `Click` event is
+    /// pending. `since` is the release instant.
+    WaitForMultiClick { clicks: u8, since: I },
+
+    /// The button exceeded `max_press_duration` and is quarantined until it is
+    /// released. `since` is the press instant.
+    Stuck { since: I },
+
+    /// The gesture was cancelled while the button was held down; nothing more
+    /// is reported until it is released. `since` is the press instant.
+    Suppressed { since: I },
+
+    /// A hold-to-confirm press is held but has not yet lasted
+    /// `confirm_duration`. `since` is the press instant.
+    Confirming { since: I },
+
+    /// A hold-to-confirm press fired `Confirmed` and is still held. `since` is
+    /// the press instant.
+    Confirmed { since: I },
+
+    /// A confirmed press was released at `since` and presses are ignored until
+    /// `confirm_cooldown` has elapsed.
+    Cooldown { since: I },
+}
@@ -0,0 +1,306 @@
+//! Logic-analyzer capture import for offline tuning.
+//!
+//! Parses VCD files and sigrok CSV exports into a timestamped level stream,
+//! and runs that stream through a `ButtHead` with a given [`Config`] so
+//! `click_timeout`, `hold_delay` and friends can be tuned against real user
+//! data.
+//!
+//! The stream is used as captured: bouncy contacts produce bouncy input, so
+//! debounce the capture (or record downstream of the debouncer) first.
+//! Timestamps are reduced to the simulation's millisecond resolution.
+
+use std::fmt;
+use std::string::String;
+use std::vec::Vec;
+
+use crate::Config;
+use crate::sim::{SimDuration, SimEvent, SimInstant};
+use crate::{ButtHead, driver};
+
+/// How long [`run`] keeps going after the last level change.
+pub const TAIL_MS: u64 = 10_000;
+
+/// A level change in a capture. `at_ns` is nanoseconds since the start of the
+/// capture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LevelChange {
+    pub at_ns: u64,
+    pub level: bool,
+}
+
+/// Error returned when a capture cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// No signal or column with the requested name exists.
+    SignalNotFound(String),
+    /// The VCD `$timescale` could not be understood.
+    InvalidTimescale,
+    /// A CSV capture has neither a time column nor a known sample rate.
+    MissingSampleRate,
+    /// A line could not be parsed. The line number is 1-based.
+    InvalidLine(usize),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::SignalNotFound(name) => write!(f, "signal `{name}` not found"),
+            ParseError::InvalidTimescale => write!(f, "invalid $timescale"),
+            ParseError::MissingSampleRate => {
+                write!(f, "no time column and no sample rate")
+            }
+            ParseError::InvalidLine(line) => write!(f, "cannot parse line {line}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses a Value Change Dump and returns the changes of the 1-bit signal
+/// whose reference name is `signal` (e.g. `"D0"`). `x` and `z` values are
+/// ignored.
+pub fn parse_vcd(text: &str, signal: &str) -> Result<Vec<LevelChange>, ParseError> {
+    let mut timescale_ns: Option<f64> = None;
+    let mut id: Option<&str> = None;
+    let mut changes = Vec::new();
+    let mut now: u64 = 0;
+
+    let mut tokens = text
+        .lines()
+        .enumerate()
+        .flat_map(|(n, line)| line.split_whitespace().map(move |token| (n + 1, token)));
+
+    while let Some((line, token)) = tokens.next() {
+        match token {
+            "$timescale" => {
+                let spec: String = tokens
+                    .by_ref()
+                    .map(|(_, t)| t)
+                    .take_while(|&t| t != "$end")
+                    .collect();
+                timescale_ns = Some(parse_timescale(&spec).ok_or(ParseError::InvalidTimescale)?);
+            }
+            "$var" => {
+                let fields: Vec<&str> = tokens
+                    .by_ref()
+                    .map(|(_, t)| t)
+                    .take_while(|&t| t != "$end")
+                    .collect();
+                // $var <type> <width> <id> <reference> [bit select] $end
+                if let [_, width, var_id, reference, ..] = fields[..]
+                    && reference == signal
+                    && width == "1"
+                {
+                    id = Some(var_id);
+                }
+            }
+            "$dumpvars" | "$dumpon" | "$dumpoff" | "$dumpall" | "$end" => {}
+            t if t.starts_with('$') => {
+                // $comment, $date, $version, $scope, ... — skip to $end.
+                for (_, t) in tokens.by_ref() {
+                    if t == "$end" {
+                        break;
+                    }
+                }
+            }
+            t if t.starts_with('#') => {
+                now = t[1..].parse().map_err(|_| ParseError::InvalidLine(line))?;
+            }
+            t if t.starts_with(['b', 'B', 'r', 'R']) => {
+                // Vector or real value change; skip its identifier.
+                tokens.next();
+            }
+            t => {
+                let Some(first) = t.chars().next() else {
+                    continue;
+                };
+                let (value, var_id) = t.split_at(first.len_utf8());
+                if Some(var_id) == id {
+                    let level = match value {
+                        "0" => false,
+                        "1" => true,
+                        "x" | "X" | "z" | "Z" => continue,
+                        _ => return Err(ParseError::InvalidLine(line)),
+                    };
+                    let scale = timescale_ns.unwrap_or(1.0);
+                    push_change(&mut changes, (now as f64 * scale) as u64, level);
+                }
+            }
+        }
+    }
+
+    if id.is_none() {
+        return Err(ParseError::SignalNotFound(signal.into()));
+    }
+    Ok(changes)
+}
+
+/// Parses a sigrok CSV export and returns the changes of column `channel`
+/// (e.g. `"D0"`).
+///
+/// Lines starting with `;` are comments. The first other line is the header.
+/// If it has a time column its values are used, in the unit given by the
+/// header (`Time [s]`, `Time [ms]`, `Time [us]` or `Time [ns]`; a bare `Time`
+/// is in seconds). An unknown unit is reported as an invalid header line.
+/// Without a time column, samples are timed by `samplerate_hz`, or by the
+/// `; Samplerate: ...` comment if `samplerate_hz` is `None`.
+pub fn parse_sigrok_csv(
+    text: &str,
+    channel: &str,
+    samplerate_hz: Option<u64>,
+) -> Result<Vec<LevelChange>, ParseError> {
+    let mut samplerate = samplerate_hz;
+    let mut columns: Option<(usize, Option<(usize, f64)>)> = None;
+    let mut changes = Vec::new();
+    let mut sample: u64 = 0;
+
+    for (n, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(comment) = line.strip_prefix(';') {
+            if samplerate.is_none() {
+                samplerate = parse_samplerate_comment(comment);
+            }
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        let Some((value_col, time_col)) = columns else {
+            let value_col = fields
+                .iter()
+                .position(|&f| f == channel)
+                .ok_or_else(|| ParseError::SignalNotFound(channel.into()))?;
+            let time_col = match fields
+                .iter()
+                .position(|f| f.to_ascii_lowercase().starts_with("time"))
+            {
+                Some(col) => {
+                    let unit_ns =
+                        parse_time_header(fields[col]).ok_or(ParseError::InvalidLine(n + 1))?;
+                    Some((col, unit_ns))
+                }
+                None => None,
+            };
+            if time_col.is_none() && samplerate.is_none() {
+                return Err(ParseError::MissingSampleRate);
+            }
+            columns = Some((value_col, time_col));
+            continue;
+        };
+
+        let invalid = || ParseError::InvalidLine(n + 1);
+        let level = match fields.get(value_col).copied() {
+            Some("0") => false,
+            Some("1") => true,
+            _ => return Err(invalid()),
+        };
+        let at_ns = match (time_col, samplerate) {
+            (Some((col, unit_ns)), _) => {
+                let time: f64 = fields
+                    .get(col)
+                    .and_then(|t| t.parse().ok())
+                    .ok_or_else(invalid)?;
+                (time * unit_ns) as u64
+            }
+            (None, Some(rate)) => sample.saturating_mul(1_000_000_000) / rate.max(1),
+            (None, None) => return Err(ParseError::MissingSampleRate),
+        };
+        sample += 1;
+        push_change(&mut changes, at_ns, level);
+    }
+
+    if columns.is_none() {
+        return Err(ParseError::SignalNotFound(channel.into()));
+    }
+    Ok(changes)
+}
+
+/// Runs a level stream through a fresh `ButtHead` with `config` and returns
+/// the resulting events. Levels are passed to `update()` as raw pin states,
+/// so `config.active_low` must match the captured wiring. The capture's time
+/// origin is the first change.
+pub fn run(
+    config: &'static Config<SimDuration>,
+    changes: &[LevelChange],
+) -> Vec<(SimInstant, SimEvent)> {
+    let origin = changes.first().map_or(0, |c| c.at_ns);
+    let mut events = Vec::new();
+    driver::drive(
+        &mut ButtHead::new(config),
+        SimInstant(0),
+        changes
+            .iter()
+            .map(|c| (c.at_ns.saturating_sub(origin) / 1_000_000, c.level)),
+        None,
+        TAIL_MS,
+        |at, event| events.push((at, event)),
+    );
+    events
+}
+
+/// Appends a change unless the level is unchanged.
+fn push_change(changes: &mut Vec<LevelChange>, at_ns: u64, level: bool) {
+    if changes.last().is_none_or(|last| last.level != level) {
+        changes.push(LevelChange { at_ns, level });
+    }
+}
+
+/// Parses e.g. `"1ns"`, `"10 us"` into nanoseconds per tick.
+fn parse_timescale(spec: &str) -> Option<f64> {
+    let spec = spec.trim();
+    let split = spec.find(|c: char| !c.is_ascii_digit())?;
+    let (number, unit) = spec.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let unit_ns = match unit.trim() {
+        "s" => 1e9,
+        "ms" => 1e6,
+        "us" => 1e3,
+        "ns" => 1.0,
+        "ps" => 1e-3,
+        "fs" => 1e-6,
+        _ => return None,
+    };
+    Some(number * unit_ns)
+}
+
+/// Parses a `Time [ms]` style column header into nanoseconds per unit.
+fn parse_time_header(header: &str) -> Option<f64> {
+    // The caller matched an ASCII `time` prefix, so byte 4 is a boundary.
+    let unit = header[4..].trim();
+    let unit = match unit.strip_prefix('[') {
+        Some(rest) => rest.strip_suffix(']')?.trim(),
+        None if unit.is_empty() => "s",
+        None => return None,
+    };
+    match unit {
+        "s" => Some(1e9),
+        "ms" => Some(1e6),
+        "us" | "µs" => Some(1e3),
+        "ns" => Some(1.0),
+        _ => None,
+    }
+}
+
+/// Parses a `Samplerate: 1 MHz` style comment into Hz.
+fn parse_samplerate_comment(comment: &str) -> Option<u64> {
+    let (key, value) = comment.split_once(':')?;
+    if !key.trim().eq_ignore_ascii_case("samplerate")
+        && !key.trim().eq_ignore_ascii_case("sample rate")
+    {
+        return None;
+    }
+    let value = value.trim();
+    let split = value.find(|c: char| !(c.is_ascii_digit() || c == '.'))?;
+    let (number, unit) = value.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let multiplier = match unit.trim().to_ascii_lowercase().as_str() {
+        "hz" => 1.0,
+        "khz" => 1e3,
+        "mhz" => 1e6,
+        "ghz" => 1e9,
+        _ => return None,
+    };
+    Some((number * multiplier) as u64)
+}
@@ -2,6 +2,7 @@ use crate::TimeDuration;
 
 /// Configuration for a `ButtHead` instance.
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Config<D: TimeDuration> {
     /// Input polarity (true = pin low means pressed).
     pub active_low: bool,
@@ -15,6 +16,19 @@ pub struct Config<D: TimeDuration> {
     /// Time between subsequent Hold events while held.
     pub hold_interval: D,
 
+    /// Named hold milestones, as ascending offsets from the press (e.g. 2 s
+    /// for a menu, 5 s to reset the network, 10 s for a factory reset).
+    ///
+    /// - `&[]` — `Hold` fires after `hold_delay`, then every `hold_interval`.
+    /// - Non-empty — replaces `hold_delay` and `hold_interval`:
+    ///   `Event::HoldStage { index, .. }` fires once when the press reaches
+    ///   `hold_stages[index]`, and no `Hold` events fire. A release before the
+    ///   first stage is a click. At most 256 stages are distinguished.
+    ///
+    /// Not serialized; deserialized configs have no stages.
+    #[cfg_attr(feature = "serde", serde(skip, default = "no_hold_stages"))]
+    pub hold_stages: &'static [D],
+
     /// Maximum number of clicks to accumulate before emitting a `Click` event
     /// immediately (without waiting for `click_timeout` to expire).
     ///
@@ -22,4 +36,120 @@ pub struct Config<D: TimeDuration> {
     /// - `Some(1)` — emit `Click` on every release with no timeout wait.
     /// - `Some(n)` — emit immediately once the n-th click in a sequence lands.
     pub max_click_count: Option<u8>,
+
+    /// Maximum time the button may stay pressed before it is considered stuck
+    /// (shorted contact, pinched cable, ...).
+    ///
+    /// - `None` — no limit; `Hold` events repeat for as long as the press lasts.
+    /// - `Some(d)` — after `d`, `Event::Stuck` fires once and the button emits
+    ///   no further events until it is physically released.
+    pub max_press_duration: Option<D>,
+
+    /// How `update()` and `try_update()` recover when `now` goes backwards or
+    /// a deadline cannot be represented by the instant type.
+    /// See [`TimeFaultPolicy`].
+    pub time_fault_policy: TimeFaultPolicy,
+
+    /// What `ButtHead::enable()` does with a press that began while the button
+    /// was disabled.
+    ///
+    /// - `false` — the press is adopted as a new press starting at the
+    ///   `enable()` call; the next update emits `Press`.
+    /// - `true` — the press is ignored; the button becomes active once it has
+    ///   been released.
+    pub require_release_on_enable: bool,
+
+    /// Shortest press that counts. Isolated pulses shorter than this (e.g.
+    /// EMI on long cable runs) are rejected: they emit no `Press` and do not
+    /// count toward `click_count`. `Press` is emitted once the press has
+    /// lasted this long, still carrying the original press instant.
+    ///
+    /// - `None` — every press counts and `Press` fires on the edge.
+    pub min_press_duration: Option<D>,
+
+    /// Report presses rejected by `min_press_duration` as `Event::Glitch`,
+    /// e.g. for field diagnostics.
+    pub report_glitches: bool,
+
+    /// Hold-to-confirm mode for destructive actions: the button must be held
+    /// this long before `Event::Confirmed` fires.
+    ///
+    /// - `None` — normal click and hold gestures.
+    /// - `Some(d)` — a press emits `ConfirmStarted`, then `Confirmed` once it
+    ///   has lasted `d`; releasing earlier emits `ConfirmAborted` instead.
+    ///   `Press`, `Release`, `Click`, `Hold` and `Stuck` are not emitted, and
+    ///   the hold and stuck settings are ignored.
+    pub confirm_duration: Option<D>,
+
+    /// Interval between `Event::ConfirmProgress` events while a confirm press
+    /// is held, e.g. to step a progress ring.
+    ///
+    /// - `None` — no progress events.
+    pub confirm_progress_interval: Option<D>,
+
+    /// Cool-down after a confirmed press is released, during which further
+    /// presses are ignored. A press still held when the cool-down ends stays
+    /// ignored until it is released.
+    ///
+    /// - `None` — the button is active again as soon as it is released.
+    pub confirm_cooldown: Option<D>,
+}
+
+impl<D: TimeDuration> Config<D> {
+    /// Returns a config with the given click and hold timings and everything
+    /// else at its default: active-high input, repeating `Hold`, no click
+    /// limit, no stuck detection, `TimeFaultPolicy::Clamp`, no minimum press
+    /// duration and no hold-to-confirm.
+    ///
+    /// Being `const`, it can serve as the base of a `static` config, e.g.
+    /// `Config { active_low: true, ..Config::new(click, hold, interval) }`, so
+    /// that fields added in later versions do not break it.
+    pub const fn new(click_timeout: D, hold_delay: D, hold_interval: D) -> Self {
+        Self {
+            active_low: false,
+            click_timeout,
+            hold_delay,
+            hold_interval,
+            hold_stages: &[],
+            max_click_count: None,
+            max_press_duration: None,
+            time_fault_policy: TimeFaultPolicy::Clamp,
+            require_release_on_enable: false,
+            min_press_duration: None,
+            report_glitches: false,
+            confirm_duration: None,
+            confirm_progress_interval: None,
+            confirm_cooldown: None,
+        }
+    }
+}
+
+// Spelled out so that skipping `hold_stages` does not require `D: Default`.
+#[cfg(feature = "serde")]
+fn no_hold_stages<D>() -> &'static [D] {
+    &[]
+}
+
+/// Recovery policy for clock faults.
+///
+/// Two faults are detected: `now` earlier than the previous update (requires
+/// [`TimeInstant::checked_duration_since`](crate::TimeInstant::checked_duration_since)
+/// to be implemented), and a hold deadline that overflows the instant type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TimeFaultPolicy {
+    /// Keep going: a rewound `now` is replaced by the previous one, and a hold
+    /// deadline past the end of the clock is never reached (no further `Hold`
+    /// fires for that press).
+    Clamp,
+
+    /// Abandon the current gesture without emitting events. A press in
+    /// progress is ignored until the button is released.
+    Reset,
+
+    /// Return an [`UpdateError`](crate::UpdateError) from `try_update()` and
+    /// leave the state untouched, so the call can be retried. `update()` cannot
+    /// report errors and falls back to `Clamp`.
+    Error,
 }
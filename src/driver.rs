@@ -0,0 +1,60 @@
+use crate::{ButtHead, Event, TimeDuration, TimeInstant};
+
+/// Drives `button` through a sequence of raw input changes the way
+/// well-behaved firmware would: `update()` is called at every input change and
+/// whenever the returned `ServiceTiming` asks for it.
+///
+/// `changes` yields `(offset_ms, is_pressed)` pairs in ascending order, with
+/// offsets relative to `start`. The run stops once there are no more changes
+/// and nothing is scheduled, when the next call would be more than `tail_ms`
+/// after the last change, or when it would be past `end_ms`.
+pub(crate) fn drive<I, F>(
+    button: &mut ButtHead<I>,
+    start: I,
+    changes: impl IntoIterator<Item = (u64, bool)>,
+    end_ms: Option<u64>,
+    tail_ms: u64,
+    mut on_event: F,
+) where
+    I: TimeInstant,
+    F: FnMut(I, Event<I::Duration, I>),
+{
+    let mut changes = changes.into_iter().peekable();
+    let mut level = button.is_pressed() != button.config().active_low;
+    let mut last_change = 0u64;
+    let mut deadline: Option<u64> = None;
+
+    loop {
+        let next_change = changes.peek().map(|&(at, _)| at);
+        let now_ms = match (deadline, next_change) {
+            (Some(d), Some(c)) => d.min(c),
+            (Some(d), None) => d,
+            (None, Some(c)) => c,
+            (None, None) => break,
+        };
+        if end_ms.is_some_and(|end| now_ms > end)
+            || (next_change.is_none() && now_ms > last_change.saturating_add(tail_ms))
+        {
+            break;
+        }
+
+        if next_change == Some(now_ms)
+            && let Some((at, pressed)) = changes.next()
+        {
+            level = pressed;
+            last_change = at;
+        }
+
+        let Some(now) = start.checked_add(I::Duration::from_millis(now_ms)) else {
+            break;
+        };
+        let result = button.update(level, now);
+        if let Some(event) = result.event {
+            on_event(now, event);
+        }
+        deadline = result
+            .next_service
+            .deadline(now)
+            .map(|at| now_ms + at.duration_since(now).as_millis());
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{
+    ButtHead, ButtonHandler, Config, Event, ServiceTiming, TimeDuration, TimeInstant, UpdateResult,
+};
+
+/// A [`ButtHead`] fed by a redundant pair of contacts: a normally-open (NO)
+/// and a normally-closed (NC) one, as found on safety-relevant buttons.
+///
+/// The two raw levels must be complementary. While they are, the NO level is
+/// passed to the button (`active_low` applies to it as usual). A brief
+/// disagreement, such as the contacts switching a few milliseconds apart, is
+/// tolerated for up to the discrepancy time, during which the button keeps
+/// the last consistent level. A longer one raises
+/// [`Event::ChannelFault`] once and inhibits the button, without emitting any
+/// further gesture events, until the contacts agree again. Recovery follows
+/// [`ButtHead::enable`], so `require_release_on_enable` decides whether a press
+/// held at that point is adopted.
+pub struct DualChannelButton<I: TimeInstant> {
+    button: ButtHead<I>,
+    discrepancy_time: I::Duration,
+    /// Last NO level seen while the channels were consistent.
+    agreed: bool,
+    discrepant_since: Option<I>,
+    faulted: bool,
+}
+
+impl<I: TimeInstant> DualChannelButton<I> {
+    /// Creates a dual-channel button. The channels may disagree for at most
+    /// `discrepancy_time` before a fault is raised.
+    pub fn new(config: &'static Config<I::Duration>, discrepancy_time: I::Duration) -> Self {
+        Self {
+            button: ButtHead::new(config),
+            discrepancy_time,
+            agreed: config.active_low,
+            discrepant_since: None,
+            faulted: false,
+        }
+    }
+
+    /// Returns the wrapped button.
+    pub fn button(&self) -> &ButtHead<I> {
+        &self.button
+    }
+
+    /// Returns the configured discrepancy time.
+    pub fn discrepancy_time(&self) -> I::Duration {
+        self.discrepancy_time
+    }
+
+    /// Returns `true` after a `ChannelFault`, until the contacts agree again.
+    pub fn is_faulted(&self) -> bool {
+        self.faulted
+    }
+
+    /// Feeds the raw NO and NC contact levels at `now`. Call on every change
+    /// of either level and whenever `next_service` asks for it, so that a
+    /// lasting disagreement is reported on time.
+    pub fn update(&mut self, no: bool, nc: bool, now: I) -> UpdateResult<I::Duration, I> {
+        if no != nc {
+            self.agreed = no;
+            self.discrepant_since = None;
+            if self.faulted {
+                self.faulted = false;
+                self.button.enable(no, now);
+            }
+            return self.button.update(no, now);
+        }
+
+        if self.faulted {
+            return UpdateResult {
+                event: None,
+                next_service: ServiceTiming::Idle,
+            };
+        }
+
+        let since = *self.discrepant_since.get_or_insert(now);
+        let elapsed = now.duration_since(since);
+        if elapsed.as_millis() >= self.discrepancy_time.as_millis() {
+            self.faulted = true;
+            self.discrepant_since = None;
+            self.button.disable();
+            return UpdateResult {
+                event: Some(Event::ChannelFault { no, nc }),
+                next_service: ServiceTiming::Idle,
+            };
+        }
+
+        // Within tolerance — hold the last consistent level.
+        let result = self.button.update(self.agreed, now);
+        UpdateResult {
+            event: result.event,
+            next_service: result.next_service.min(ServiceTiming::Delay(
+                self.discrepancy_time.saturating_sub(elapsed),
+            )),
+        }
+    }
+
+    /// Like [`DualChannelButton::update`], but dispatches the event to
+    /// `handler` (see [`ButtHead::update_with`]).
+    pub fn update_with<H: ButtonHandler<I>>(
+        &mut self,
+        handler: &mut H,
+        no: bool,
+        nc: bool,
+        now: I,
+    ) -> ServiceTiming<I::Duration> {
+        let result = self.update(no, nc, now);
+        if let Some(event) = result.event {
+            self.button.dispatch(handler, event);
+        }
+        result.next_service
+    }
+}
@@ -1,8 +1,13 @@
 use crate::{TimeDuration, TimeInstant};
 
 /// A button event produced by the state machine.
+///
+/// With the `serde` feature, variants serialize externally tagged; compact
+/// formats such as `postcard` encode them by declaration index, so new
+/// variants are only ever appended.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Event<D: TimeDuration, I: TimeInstant<Duration = D>> {
     /// The button was pressed. Fires immediately on press edge.
     /// `at` is the timestamp of the press, identical to what
@@ -23,5 +28,54 @@ pub enum Event<D: TimeDuration, I: TimeInstant<Duration = D>> {
     /// `clicks_before` is the number of clicks that preceded this hold
     /// (0 = plain hold, 1 = click+hold, 2 = double-click+hold, ...).
     /// `level` increments on each repeat (0 = first hold event, 1 = second, ...).
-    Hold { clicks_before: u8, level: u8 },
+    Hold { clicks_before: u8, level: u32 },
+
+    /// A hold gesture ended. Fires on the call after the
+    /// `Release { click_follows: false, .. }` that ends a hold.
+    /// `clicks_before` matches the preceding `Hold` events, `levels` is the
+    /// number of `Hold` events that fired, and `duration` is the total time the
+    /// button was held.
+    HoldEnd {
+        clicks_before: u8,
+        levels: u32,
+        duration: D,
+    },
+
+    /// The button has been pressed for longer than `max_press_duration` and is
+    /// assumed to be faulty. `duration` is how long it had been pressed. No
+    /// further events fire (not even `Release`) until the button is released.
+    Stuck { duration: D },
+
+    /// A press shorter than `min_press_duration` was rejected. `duration` is
+    /// how long it lasted. Only reported when `report_glitches` is set; the
+    /// press emits no other event and does not count toward a multi-click.
+    Glitch { duration: D },
+
+    /// The press reached `hold_stages[index]`. Fires once per stage, in place
+    /// of `Hold`, when `hold_stages` is configured. `clicks_before` is as for
+    /// `Hold`.
+    HoldStage { index: u8, clicks_before: u8 },
+
+    /// A hold-to-confirm press began (`confirm_duration` is set). Fires in
+    /// place of `Press`; `at` is the timestamp of the press.
+    ConfirmStarted { at: I },
+
+    /// A hold-to-confirm press is still held. Fires every
+    /// `confirm_progress_interval`; `elapsed` is how long it has been held.
+    ConfirmProgress { elapsed: D },
+
+    /// A hold-to-confirm press lasted `confirm_duration`. Fires once; the
+    /// release that follows emits nothing.
+    Confirmed,
+
+    /// A hold-to-confirm press was released before `confirm_duration`.
+    /// `held_for` is how long it was held. No `Click` follows.
+    ConfirmAborted { held_for: D },
+
+    /// The normally-open and normally-closed contacts of a
+    /// [`DualChannelButton`](crate::DualChannelButton) disagreed for longer
+    /// than the discrepancy time. `no` and `nc` are their raw levels. Fires
+    /// once; the button is inhibited until the contacts agree again. Like
+    /// `Stuck`, it ends any press in progress without a `Release`.
+    ChannelFault { no: bool, nc: bool },
 }
@@ -0,0 +1,92 @@
+use crate::state_machine::StateMachine;
+use crate::{ButtonState, TimeInstant};
+
+/// Gives [`ButtonHandler`] callbacks controlled access to the button that
+/// produced the event.
+pub struct HandlerContext<'a, I: TimeInstant> {
+    state_machine: &'a mut StateMachine<I>,
+}
+
+impl<'a, I: TimeInstant> HandlerContext<'a, I> {
+    pub(crate) fn new(state_machine: &'a mut StateMachine<I>) -> Self {
+        Self { state_machine }
+    }
+
+    /// Cancels the pending `Click`. Same as
+    /// [`ButtHead::cancel_pending_click`](crate::ButtHead::cancel_pending_click);
+    /// call it from [`ButtonHandler::on_release`] when `click_follows` is
+    /// `true`.
+    pub fn cancel_pending_click(&mut self) -> bool {
+        self.state_machine.cancel_pending_click()
+    }
+
+    /// Cancels the gesture in progress. Same as
+    /// [`ButtHead::cancel_gesture`](crate::ButtHead::cancel_gesture); e.g. call
+    /// it from [`ButtonHandler::on_hold`] to stop further holds.
+    pub fn cancel_gesture(&mut self) -> bool {
+        self.state_machine.cancel_gesture()
+    }
+
+    /// Returns the gesture state after the event was processed.
+    pub fn state(&self) -> ButtonState<I> {
+        self.state_machine.state()
+    }
+}
+
+/// Callback-style alternative to matching on [`Event`](crate::Event).
+///
+/// Every method has a no-op default, so implement only the gestures you care
+/// about and pass the handler to [`ButtHead::update_with`](crate::ButtHead::update_with).
+pub trait ButtonHandler<I: TimeInstant> {
+    /// See [`Event::Press`](crate::Event::Press).
+    fn on_press(&mut self, _ctx: &mut HandlerContext<'_, I>, _at: I) {}
+
+    /// See [`Event::Release`](crate::Event::Release).
+    fn on_release(
+        &mut self,
+        _ctx: &mut HandlerContext<'_, I>,
+        _duration: I::Duration,
+        _click_follows: bool,
+    ) {
+    }
+
+    /// See [`Event::Click`](crate::Event::Click).
+    fn on_click(&mut self, _ctx: &mut HandlerContext<'_, I>, _count: u8) {}
+
+    /// See [`Event::Hold`](crate::Event::Hold).
+    fn on_hold(&mut self, _ctx: &mut HandlerContext<'_, I>, _clicks_before: u8, _level: u32) {}
+
+    /// See [`Event::HoldStage`](crate::Event::HoldStage).
+    fn on_hold_stage(&mut self, _ctx: &mut HandlerContext<'_, I>, _index: u8, _clicks_before: u8) {}
+
+    /// See [`Event::HoldEnd`](crate::Event::HoldEnd).
+    fn on_hold_end(
+        &mut self,
+        _ctx: &mut HandlerContext<'_, I>,
+        _clicks_before: u8,
+        _levels: u32,
+        _duration: I::Duration,
+    ) {
+    }
+
+    /// See [`Event::Stuck`](crate::Event::Stuck).
+    fn on_stuck(&mut self, _ctx: &mut HandlerContext<'_, I>, _duration: I::Duration) {}
+
+    /// See [`Event::Glitch`](crate::Event::Glitch).
+    fn on_glitch(&mut self, _ctx: &mut HandlerContext<'_, I>, _duration: I::Duration) {}
+
+    /// See [`Event::ConfirmStarted`](crate::Event::ConfirmStarted).
+    fn on_confirm_started(&mut self, _ctx: &mut HandlerContext<'_, I>, _at: I) {}
+
+    /// See [`Event::ConfirmProgress`](crate::Event::ConfirmProgress).
+    fn on_confirm_progress(&mut self, _ctx: &mut HandlerContext<'_, I>, _elapsed: I::Duration) {}
+
+    /// See [`Event::Confirmed`](crate::Event::Confirmed).
+    fn on_confirmed(&mut self, _ctx: &mut HandlerContext<'_, I>) {}
+
+    /// See [`Event::ConfirmAborted`](crate::Event::ConfirmAborted).
+    fn on_confirm_aborted(&mut self, _ctx: &mut HandlerContext<'_, I>, _held_for: I::Duration) {}
+
+    /// See [`Event::ChannelFault`](crate::Event::ChannelFault).
+    fn on_channel_fault(&mut self, _ctx: &mut HandlerContext<'_, I>, _no: bool, _nc: bool) {}
+}
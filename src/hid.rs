@@ -0,0 +1,290 @@
+//! USB HID report generation from button events.
+//!
+//! Produces standard 8-byte boot keyboard reports (modifier byte, reserved
+//! byte, six key slots) and 16-bit consumer-control reports. The output is
+//! plain bytes, ready to hand to any USB stack.
+
+use crate::{Event, Keymap, TimeDuration, TimeInstant};
+
+/// Length of a boot keyboard report.
+pub const KEYBOARD_REPORT_LEN: usize = 8;
+
+/// Length of a consumer-control report (one 16-bit usage, little-endian).
+pub const CONSUMER_REPORT_LEN: usize = 2;
+
+const KEY_SLOTS: usize = 6;
+
+/// What a mapped gesture does on the HID side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum HidAction {
+    /// A keyboard key (usage page 0x07) with optional modifier bits
+    /// (`MOD_LEFT_CTRL`, ...).
+    Key { modifiers: u8, keycode: u8 },
+
+    /// A consumer-control usage (usage page 0x0C), e.g. `0x00E9` (volume up).
+    Consumer(u16),
+}
+
+/// Left Control modifier bit.
+pub const MOD_LEFT_CTRL: u8 = 0x01;
+/// Left Shift modifier bit.
+pub const MOD_LEFT_SHIFT: u8 = 0x02;
+/// Left Alt modifier bit.
+pub const MOD_LEFT_ALT: u8 = 0x04;
+/// Left GUI modifier bit.
+pub const MOD_LEFT_GUI: u8 = 0x08;
+/// Right Control modifier bit.
+pub const MOD_RIGHT_CTRL: u8 = 0x10;
+/// Right Shift modifier bit.
+pub const MOD_RIGHT_SHIFT: u8 = 0x20;
+/// Right Alt modifier bit.
+pub const MOD_RIGHT_ALT: u8 = 0x40;
+/// Right GUI modifier bit.
+pub const MOD_RIGHT_GUI: u8 = 0x80;
+
+/// A single HID input report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum HidReport {
+    /// Boot keyboard report: `[modifiers, 0, key1, .., key6]`.
+    Keyboard([u8; KEYBOARD_REPORT_LEN]),
+
+    /// Consumer-control report: the active usage, little-endian (0 = none).
+    Consumer([u8; CONSUMER_REPORT_LEN]),
+}
+
+impl HidReport {
+    /// Returns the raw report bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            HidReport::Keyboard(bytes) => bytes,
+            HidReport::Consumer(bytes) => bytes,
+        }
+    }
+}
+
+/// The reports produced by a single event, in the order they must be sent.
+/// At most two: a tap produces a press report followed by a release report,
+/// and releasing a button may update both the keyboard and the consumer
+/// report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HidReports {
+    reports: [Option<HidReport>; 2],
+    next: usize,
+}
+
+impl HidReports {
+    const NONE: Self = Self {
+        reports: [None, None],
+        next: 0,
+    };
+
+    fn one(report: HidReport) -> Self {
+        Self {
+            reports: [Some(report), None],
+            next: 0,
+        }
+    }
+
+    fn two(first: HidReport, second: HidReport) -> Self {
+        Self {
+            reports: [Some(first), Some(second)],
+            next: 0,
+        }
+    }
+
+    /// Packs up to two optional reports, keeping their order.
+    fn some(first: Option<HidReport>, second: Option<HidReport>) -> Self {
+        match (first, second) {
+            (Some(first), Some(second)) => Self::two(first, second),
+            (Some(report), None) | (None, Some(report)) => Self::one(report),
+            (None, None) => Self::NONE,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.reports[self.next..].iter().all(Option::is_none)
+    }
+}
+
+impl Iterator for HidReports {
+    type Item = HidReport;
+
+    fn next(&mut self) -> Option<HidReport> {
+        let report = self.reports.get(self.next).copied().flatten();
+        self.next += 1;
+        report
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct HeldKey {
+    button: usize,
+    modifiers: u8,
+    keycode: u8,
+}
+
+/// Translates button events into HID reports through a [`Keymap`].
+///
+/// - A mapped `Click` types the key: a press report followed by a release
+///   report.
+/// - A mapped `Press` or `Hold` holds the key down until that button's next
+///   `Release`, `Stuck` or `ChannelFault`. Repeated `Hold` levels keep the
+///   key held without further reports; key repeat is left to the host.
+/// - Any other mapped event (e.g. `Release` when nothing is held) types the
+///   key.
+///
+/// For presses that end without a `Release` (see
+/// [`ButtHead`](crate::ButtHead#presses-that-end-without-release)), call
+/// [`HidKeyboard::release_button`] and send its reports, or the key stays down.
+///
+/// Keys held by several buttons are combined into one 6KRO report; a seventh
+/// simultaneous key is dropped.
+#[derive(Debug, Clone)]
+pub struct HidKeyboard {
+    held: [Option<HeldKey>; KEY_SLOTS],
+    consumer: Option<(usize, u16)>,
+}
+
+impl Default for HidKeyboard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HidKeyboard {
+    /// Creates a translator with no keys held.
+    pub const fn new() -> Self {
+        Self {
+            held: [None; KEY_SLOTS],
+            consumer: None,
+        }
+    }
+
+    /// Returns the keyboard report for the currently held keys.
+    pub fn keyboard_report(&self) -> [u8; KEYBOARD_REPORT_LEN] {
+        self.keyboard_report_with(None)
+    }
+
+    /// Returns the consumer-control report for the currently held usage.
+    pub fn consumer_report(&self) -> [u8; CONSUMER_REPORT_LEN] {
+        self.consumer.map_or(0, |(_, usage)| usage).to_le_bytes()
+    }
+
+    /// Processes `event` from the button with index `button` and returns the
+    /// reports to send.
+    pub fn process<D: TimeDuration, I: TimeInstant<Duration = D>>(
+        &mut self,
+        keymap: &Keymap<HidAction>,
+        button: usize,
+        event: &Event<D, I>,
+    ) -> HidReports {
+        // Releasing the button lets go of anything it was holding down.
+        if matches!(
+            event,
+            Event::Release { .. } | Event::Stuck { .. } | Event::ChannelFault { .. }
+        ) {
+            let reports = self.release_button(button);
+            if !reports.is_empty() {
+                return reports;
+            }
+        }
+
+        let Some(action) = keymap.lookup(button, event) else {
+            return HidReports::NONE;
+        };
+
+        match event {
+            Event::Press { .. } | Event::Hold { .. } => self
+                .hold(button, *action)
+                .map_or(HidReports::NONE, HidReports::one),
+            _ => self.tap(*action),
+        }
+    }
+
+    fn keyboard_report_with(&self, extra: Option<(u8, u8)>) -> [u8; KEYBOARD_REPORT_LEN] {
+        let mut report = [0u8; KEYBOARD_REPORT_LEN];
+        let mut slot = 2;
+        let keys = self
+            .held
+            .iter()
+            .flatten()
+            .map(|held| (held.modifiers, held.keycode))
+            .chain(extra);
+        for (modifiers, keycode) in keys {
+            report[0] |= modifiers;
+            if keycode != 0 && slot < KEYBOARD_REPORT_LEN && !report[2..slot].contains(&keycode) {
+                report[slot] = keycode;
+                slot += 1;
+            }
+        }
+        report
+    }
+
+    /// Press report followed by release report.
+    fn tap(&self, action: HidAction) -> HidReports {
+        match action {
+            HidAction::Key { modifiers, keycode } => HidReports::two(
+                HidReport::Keyboard(self.keyboard_report_with(Some((modifiers, keycode)))),
+                HidReport::Keyboard(self.keyboard_report()),
+            ),
+            HidAction::Consumer(usage) => HidReports::two(
+                HidReport::Consumer(usage.to_le_bytes()),
+                HidReport::Consumer(self.consumer_report()),
+            ),
+        }
+    }
+
+    /// Holds the key down. Returns `None` if it is already held by this
+    /// button or no slot is free.
+    fn hold(&mut self, button: usize, action: HidAction) -> Option<HidReport> {
+        match action {
+            HidAction::Key { modifiers, keycode } => {
+                let already_held = self.held.iter().flatten().any(|held| {
+                    held.button == button && held.keycode == keycode && held.modifiers == modifiers
+                });
+                if already_held {
+                    return None;
+                }
+                let slot = self.held.iter_mut().find(|slot| slot.is_none())?;
+                *slot = Some(HeldKey {
+                    button,
+                    modifiers,
+                    keycode,
+                });
+                Some(HidReport::Keyboard(self.keyboard_report()))
+            }
+            HidAction::Consumer(usage) => {
+                if self.consumer == Some((button, usage)) {
+                    return None;
+                }
+                self.consumer = Some((button, usage));
+                Some(HidReport::Consumer(usage.to_le_bytes()))
+            }
+        }
+    }
+
+    /// Lets go of everything held by `button`: its keys and its consumer
+    /// usage (e.g. a key held on `Press` and a usage held on `Hold`). Returns
+    /// the keyboard and consumer reports that changed, in that order.
+    ///
+    /// `process` does this on `Release`; call it yourself when a press ends
+    /// without one (see [`HidKeyboard`]).
+    pub fn release_button(&mut self, button: usize) -> HidReports {
+        let mut released_key = false;
+        for slot in self.held.iter_mut() {
+            if slot.is_some_and(|held| held.button == button) {
+                *slot = None;
+                released_key = true;
+            }
+        }
+        let keyboard = released_key.then(|| HidReport::Keyboard(self.keyboard_report()));
+
+        let consumer = self.consumer.is_some_and(|(b, _)| b == button).then(|| {
+            self.consumer = None;
+            HidReport::Consumer(self.consumer_report())
+        });
+        HidReports::some(keyboard, consumer)
+    }
+}
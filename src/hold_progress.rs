@@ -0,0 +1,34 @@
+use crate::TimeDuration;
+
+/// How far a press has progressed toward its next `Hold` or `HoldStage`, as
+/// returned by [`ButtHead::hold_progress`](crate::ButtHead::hold_progress).
+///
+/// Both durations are measured from the press, so a progress ring for the
+/// 5 s stage of `[2 s, 5 s]` runs from 2 s to 5 s out of 5 s once the first
+/// stage has fired.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct HoldProgress<D: TimeDuration> {
+    /// Time the button has been held, capped at `total`.
+    pub elapsed: D,
+
+    /// Time from the press to the next `Hold` or `HoldStage`.
+    pub total: D,
+}
+
+impl<D: TimeDuration> HoldProgress<D> {
+    /// Returns the time left until the next `Hold` or `HoldStage`.
+    pub fn remaining(&self) -> D {
+        self.total.saturating_sub(self.elapsed)
+    }
+
+    /// Returns the progress in thousandths (0..=1000), for driving LED ramps
+    /// without floating point.
+    pub fn permille(&self) -> u16 {
+        let total = self.total.as_millis();
+        if total == 0 {
+            return 1000;
+        }
+        (self.elapsed.as_millis().saturating_mul(1000) / total) as u16
+    }
+}
@@ -0,0 +1,179 @@
+use crate::{Event, TimeDuration, TimeInstant};
+
+/// Matches a single value in a [`Gesture`] pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Match<T> {
+    /// Matches any value (wildcard).
+    Any,
+
+    /// Matches exactly this value.
+    Exactly(T),
+
+    /// Matches any value in the inclusive range `min..=max`.
+    Between(T, T),
+
+    /// Matches this value or anything greater.
+    AtLeast(T),
+}
+
+impl<T: PartialOrd> Match<T> {
+    /// Returns `true` if `value` satisfies this pattern.
+    pub fn matches(&self, value: &T) -> bool {
+        match self {
+            Match::Any => true,
+            Match::Exactly(v) => value == v,
+            Match::Between(min, max) => min <= value && value <= max,
+            Match::AtLeast(min) => min <= value,
+        }
+    }
+}
+
+/// A gesture pattern, matched against [`Event`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Gesture {
+    /// Matches `Event::Press`.
+    Press,
+
+    /// Matches `Event::Release`.
+    Release,
+
+    /// Matches `Event::Click` whose `count` satisfies the pattern.
+    Click { count: Match<u8> },
+
+    /// Matches `Event::Hold` whose `clicks_before` and `level` satisfy the
+    /// patterns.
+    Hold {
+        clicks_before: Match<u8>,
+        level: Match<u32>,
+    },
+
+    /// Matches `Event::HoldEnd` whose `clicks_before` satisfies the pattern.
+    HoldEnd { clicks_before: Match<u8> },
+
+    /// Matches `Event::Stuck`.
+    Stuck,
+
+    /// Matches `Event::Glitch` of any duration.
+    Glitch,
+
+    /// Matches `Event::HoldStage` whose `index` and `clicks_before` satisfy
+    /// the patterns.
+    HoldStage {
+        index: Match<u8>,
+        clicks_before: Match<u8>,
+    },
+
+    /// Matches `Event::ConfirmStarted`.
+    ConfirmStarted,
+
+    /// Matches `Event::ConfirmProgress` at any elapsed time.
+    ConfirmProgress,
+
+    /// Matches `Event::Confirmed`.
+    Confirmed,
+
+    /// Matches `Event::ConfirmAborted`.
+    ConfirmAborted,
+
+    /// Matches `Event::ChannelFault`.
+    ChannelFault,
+}
+
+impl Gesture {
+    /// Returns `true` if `event` is an instance of this gesture.
+    pub fn matches<D: TimeDuration, I: TimeInstant<Duration = D>>(
+        &self,
+        event: &Event<D, I>,
+    ) -> bool {
+        match (self, event) {
+            (Gesture::Press, Event::Press { .. }) => true,
+            (Gesture::Release, Event::Release { .. }) => true,
+            (Gesture::Click { count }, Event::Click { count: c }) => count.matches(c),
+            (
+                Gesture::Hold {
+                    clicks_before,
+                    level,
+                },
+                Event::Hold {
+                    clicks_before: cb,
+                    level: l,
+                },
+            ) => clicks_before.matches(cb) && level.matches(l),
+            (
+                Gesture::HoldEnd { clicks_before },
+                Event::HoldEnd {
+                    clicks_before: cb, ..
+                },
+            ) => clicks_before.matches(cb),
+            (Gesture::Stuck, Event::Stuck { .. }) => true,
+            (Gesture::Glitch, Event::Glitch { .. }) => true,
+            (
+                Gesture::HoldStage {
+                    index,
+                    clicks_before,
+                },
+                Event::HoldStage {
+                    index: i,
+                    clicks_before: cb,
+                },
+            ) => index.matches(i) && clicks_before.matches(cb),
+            (Gesture::ConfirmStarted, Event::ConfirmStarted { .. }) => true,
+            (Gesture::ConfirmProgress, Event::ConfirmProgress { .. }) => true,
+            (Gesture::Confirmed, Event::Confirmed) => true,
+            (Gesture::ConfirmAborted, Event::ConfirmAborted { .. }) => true,
+            (Gesture::ChannelFault, Event::ChannelFault { .. }) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Maps a gesture on a button to an application action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Binding<A> {
+    /// Index of the button this binding applies to.
+    pub button: Match<usize>,
+    /// The gesture that triggers the action.
+    pub gesture: Gesture,
+    /// The action to return on a match.
+    pub action: A,
+}
+
+/// A declarative table mapping `(button, gesture)` pairs to actions.
+///
+/// Build it in a `static` so behaviour can be changed by editing a table
+/// rather than `match` arms. Bindings are checked in order; the first match
+/// wins.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Keymap<A: 'static> {
+    bindings: &'static [Binding<A>],
+}
+
+impl<A> Keymap<A> {
+    /// Creates a keymap from a table of bindings. Earlier bindings take
+    /// precedence over later ones.
+    pub const fn new(bindings: &'static [Binding<A>]) -> Self {
+        Self { bindings }
+    }
+
+    /// Returns the bindings of this keymap.
+    pub const fn bindings(&self) -> &'static [Binding<A>] {
+        self.bindings
+    }
+
+    /// Returns the action of the first binding matching `event` on the button
+    /// with index `button`, or `None` if no binding matches.
+    pub fn lookup<D: TimeDuration, I: TimeInstant<Duration = D>>(
+        &self,
+        button: usize,
+        event: &Event<D, I>,
+    ) -> Option<&'static A> {
+        self.bindings
+            .iter()
+            .find(|binding| binding.button.matches(&button) && binding.gesture.matches(event))
+            .map(|binding| &binding.action)
+    }
+}
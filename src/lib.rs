@@ -6,6 +6,12 @@
 
 #![no_std]
 
+#[cfg(feature = "sim")]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
 mod time;
 pub use time::{TimeDuration, TimeInstant};
 
@@ -16,9 +22,52 @@ mod service_timing;
 pub use service_timing::ServiceTiming;
 
 mod config;
-pub use config::Config;
+pub use config::{Config, TimeFaultPolicy};
+
+mod button_state;
+pub use button_state::ButtonState;
+
+mod hold_progress;
+pub use hold_progress::HoldProgress;
 
 mod state_machine;
 
+mod snapshot;
+pub use snapshot::{RestoreError, SNAPSHOT_LEN, SNAPSHOT_VERSION};
+
+mod handler;
+pub use handler::{ButtonHandler, HandlerContext};
+
+mod keymap;
+pub use keymap::{Binding, Gesture, Keymap, Match};
+
+#[cfg(feature = "hid")]
+pub mod hid;
+
+#[cfg(feature = "midi")]
+pub mod midi;
+
+#[cfg(feature = "sim")]
+pub mod sim;
+
+mod driver;
+
+#[cfg(feature = "std")]
+pub mod capture;
+
+#[cfg(feature = "std")]
+pub mod tuning;
+
+pub mod trace;
+
 mod butt_head;
-pub use butt_head::{ButtHead, UpdateResult};
+pub use butt_head::{ButtHead, UpdateError, UpdateResult};
+
+mod dual_channel;
+pub use dual_channel::DualChannelButton;
+
+mod two_hand;
+pub use two_hand::{TwoHandControl, TwoHandEvent, TwoHandFault, TwoHandResult};
+
+mod radio;
+pub use radio::{RadioEvent, RadioGroup, RadioResult, SelectOn, TieBreak};
@@ -0,0 +1,158 @@
+//! MIDI message output for button events.
+//!
+//! Converts events into raw MIDI channel messages that the caller sends over
+//! any transport (DIN serial, USB MIDI, BLE MIDI, ...).
+
+use crate::{Event, TimeDuration, TimeInstant};
+
+const NOTE_OFF: u8 = 0x80;
+const NOTE_ON: u8 = 0x90;
+const CONTROL_CHANGE: u8 = 0xB0;
+const PROGRAM_CHANGE: u8 = 0xC0;
+
+const DATA_MAX: u8 = 0x7F;
+
+/// A raw MIDI channel message: three bytes, or two for a program change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MidiMessage {
+    bytes: [u8; 3],
+    len: u8,
+}
+
+impl MidiMessage {
+    fn three(status: u8, data1: u8, data2: u8) -> Self {
+        Self {
+            bytes: [status, data1 & DATA_MAX, data2 & DATA_MAX],
+            len: 3,
+        }
+    }
+
+    fn two(status: u8, data1: u8) -> Self {
+        Self {
+            bytes: [status, data1 & DATA_MAX, 0],
+            len: 2,
+        }
+    }
+
+    /// Returns the message bytes, status byte first.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len as usize]
+    }
+}
+
+/// What `Press` and `Release` send.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PressMapping {
+    /// Send nothing.
+    None,
+
+    /// Note On on press, Note Off on release.
+    Note { note: u8 },
+
+    /// Control change with value `on` on press and `off` on release (e.g. a
+    /// sustain pedal: controller 64, 127 / 0).
+    ControlChange { controller: u8, on: u8, off: u8 },
+}
+
+/// Maps `Hold` events onto a control-change ramp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct HoldRamp {
+    /// Controller number.
+    pub controller: u8,
+    /// Value sent for hold level 0.
+    pub start: u8,
+    /// Value added per hold level. The value saturates at 127.
+    pub step: u8,
+}
+
+/// Configuration for translating events into MIDI messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MidiConfig {
+    /// MIDI channel, 0–15 (shown as 1–16 by most devices).
+    pub channel: u8,
+
+    /// Velocity for Note On and Note Off.
+    pub velocity: u8,
+
+    /// What `Press` and `Release` send.
+    pub press: PressMapping,
+
+    /// Program sent for `Click { count: 1 }`; `count: n` sends
+    /// `program + n - 1`. `None` disables program changes.
+    pub click_program: Option<u8>,
+
+    /// Control-change ramp driven by `Hold { level }`. `None` disables it.
+    pub hold_ramp: Option<HoldRamp>,
+}
+
+impl MidiConfig {
+    /// Returns the MIDI message for `event`, or `None` if it maps to nothing.
+    ///
+    /// `Stuck` and `ChannelFault` send the release message so no note is left
+    /// hanging. Presses that end without any of these events need
+    /// [`MidiConfig::release_message`].
+    pub fn translate<D: TimeDuration, I: TimeInstant<Duration = D>>(
+        &self,
+        event: &Event<D, I>,
+    ) -> Option<MidiMessage> {
+        let channel = self.channel & 0x0F;
+        match *event {
+            Event::Press { .. } => match self.press {
+                PressMapping::None => None,
+                PressMapping::Note { note } => {
+                    Some(MidiMessage::three(NOTE_ON | channel, note, self.velocity))
+                }
+                PressMapping::ControlChange { controller, on, .. } => {
+                    Some(MidiMessage::three(CONTROL_CHANGE | channel, controller, on))
+                }
+            },
+            Event::Release { .. } | Event::Stuck { .. } | Event::ChannelFault { .. } => {
+                self.release_message()
+            }
+            Event::Click { count } => self.click_program.map(|program| {
+                let program = program.saturating_add(count.saturating_sub(1));
+                MidiMessage::two(PROGRAM_CHANGE | channel, program.min(DATA_MAX))
+            }),
+            Event::Hold { level, .. } => self.hold_ramp.map(|ramp| {
+                let value = u32::from(ramp.step)
+                    .saturating_mul(level)
+                    .saturating_add(u32::from(ramp.start))
+                    .min(u32::from(DATA_MAX)) as u8;
+                MidiMessage::three(CONTROL_CHANGE | channel, ramp.controller, value)
+            }),
+            Event::HoldEnd { .. }
+            | Event::Glitch { .. }
+            | Event::HoldStage { .. }
+            | Event::ConfirmStarted { .. }
+            | Event::ConfirmProgress { .. }
+            | Event::Confirmed
+            | Event::ConfirmAborted { .. } => None,
+        }
+    }
+
+    /// Returns the Note Off or CC off message that `Release` sends, or `None`
+    /// if `press` maps to nothing.
+    ///
+    /// Send it for presses that end without a `Release` (see
+    /// [`ButtHead`](crate::ButtHead#presses-that-end-without-release)), or the note stays on.
+    pub fn release_message(&self) -> Option<MidiMessage> {
+        let channel = self.channel & 0x0F;
+        match self.press {
+            PressMapping::None => None,
+            PressMapping::Note { note } => {
+                Some(MidiMessage::three(NOTE_OFF | channel, note, self.velocity))
+            }
+            PressMapping::ControlChange {
+                controller, off, ..
+            } => Some(MidiMessage::three(
+                CONTROL_CHANGE | channel,
+                controller,
+                off,
+            )),
+        }
+    }
+}
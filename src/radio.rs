@@ -0,0 +1,159 @@
+use crate::{ButtHead, Config, Event, ServiceTiming, TimeDuration, TimeInstant};
+
+/// Which gesture selects a button in a [`RadioGroup`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SelectOn {
+    /// `Event::Press`.
+    Press,
+
+    /// `Event::Click` of any count. Set `max_click_count: Some(1)` to select
+    /// without waiting for `click_timeout`.
+    Click,
+
+    /// The first `Event::Hold` (or `Event::HoldStage` 0) of a press.
+    Hold,
+}
+
+/// How a [`RadioGroup`] picks between buttons that select in the same
+/// update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TieBreak {
+    /// The button pressed first wins, by press instant; equal instants go to
+    /// the lowest index.
+    FirstPressed,
+
+    /// The lowest index wins.
+    LowestIndex,
+}
+
+/// An event produced by [`RadioGroup`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RadioEvent {
+    /// The button at `index` became the selected one. `previous` is the
+    /// button that was selected before, if any.
+    Selected {
+        index: usize,
+        previous: Option<usize>,
+    },
+}
+
+/// The result of a single [`RadioGroup::update`] call.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RadioResult<D: TimeDuration> {
+    /// The event produced by this update, if any.
+    pub event: Option<RadioEvent>,
+    /// When to call `update()` again. See [`ServiceTiming`].
+    pub next_service: ServiceTiming<D>,
+}
+
+/// A group of `N` mutually exclusive buttons, like radio buttons: selecting
+/// one deselects the others.
+///
+/// Selecting the button that is already selected emits nothing. The buttons'
+/// own gesture events are not reported.
+pub struct RadioGroup<I: TimeInstant, const N: usize> {
+    buttons: [ButtHead<I>; N],
+    /// Press instant of each button's current or most recent press.
+    pressed_at: [Option<I>; N],
+    selected: Option<usize>,
+    select_on: SelectOn,
+    tie_break: TieBreak,
+}
+
+impl<I: TimeInstant, const N: usize> RadioGroup<I, N> {
+    /// Creates a group of `N` buttons sharing `config`, with nothing selected.
+    pub fn new(
+        config: &'static Config<I::Duration>,
+        select_on: SelectOn,
+        tie_break: TieBreak,
+    ) -> Self {
+        Self {
+            buttons: core::array::from_fn(|_| ButtHead::new(config)),
+            pressed_at: [None; N],
+            selected: None,
+            select_on,
+            tie_break,
+        }
+    }
+
+    /// Returns the index of the selected button, if any.
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// Returns the button at `index`, or `None` if out of range.
+    pub fn button(&self, index: usize) -> Option<&ButtHead<I>> {
+        self.buttons.get(index)
+    }
+
+    /// Selects the button at `index` without any input, e.g. to restore a
+    /// saved mode. Returns the resulting event, or `None` if `index` is out of
+    /// range or already selected.
+    pub fn select(&mut self, index: usize) -> Option<RadioEvent> {
+        if index >= N || self.selected == Some(index) {
+            return None;
+        }
+        let previous = self.selected.replace(index);
+        Some(RadioEvent::Selected { index, previous })
+    }
+
+    /// Clears the selection without emitting an event.
+    pub fn clear(&mut self) {
+        self.selected = None;
+    }
+
+    /// Feeds the raw pin level of every button at `now`. Call on every input
+    /// change and whenever `next_service` asks for it.
+    pub fn update(&mut self, levels: [bool; N], now: I) -> RadioResult<I::Duration> {
+        let mut next_service = ServiceTiming::Idle;
+        let mut winner: Option<usize> = None;
+
+        for (index, (button, level)) in self.buttons.iter_mut().zip(levels).enumerate() {
+            let result = button.update(level, now);
+            next_service = next_service.min(result.next_service);
+
+            let Some(event) = result.event else {
+                continue;
+            };
+            if let Event::Press { at } = event {
+                self.pressed_at[index] = Some(at);
+            }
+            let selects = matches!(
+                (self.select_on, event),
+                (SelectOn::Press, Event::Press { .. })
+                    | (SelectOn::Click, Event::Click { .. })
+                    | (SelectOn::Hold, Event::Hold { level: 0, .. })
+                    | (SelectOn::Hold, Event::HoldStage { index: 0, .. })
+            );
+            if !selects {
+                continue;
+            }
+
+            winner = match (winner, self.tie_break) {
+                (None, _) => Some(index),
+                (Some(current), TieBreak::FirstPressed)
+                    if Self::pressed_earlier(&self.pressed_at, index, current, now) =>
+                {
+                    Some(index)
+                }
+                (Some(current), _) => Some(current),
+            };
+        }
+
+        RadioResult {
+            event: winner.and_then(|index| self.select(index)),
+            next_service,
+        }
+    }
+
+    /// Returns `true` if button `a` was pressed strictly before button `b`.
+    fn pressed_earlier(pressed_at: &[Option<I>; N], a: usize, b: usize, now: I) -> bool {
+        let age =
+            |index: usize| pressed_at[index].map_or(0, |at| now.duration_since(at).as_millis());
+        age(a) > age(b)
+    }
+}
@@ -1,8 +1,9 @@
-use crate::TimeDuration;
+use crate::{TimeDuration, TimeInstant};
 
 /// Indicates when `update()` should next be called.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ServiceTiming<D: TimeDuration> {
     /// Call back as soon as possible.
     Immediate,
@@ -15,6 +16,21 @@ pub enum ServiceTiming<D: TimeDuration> {
 }
 
 impl<D: TimeDuration> ServiceTiming<D> {
+    /// Converts the hint into an absolute deadline, given the `now` that was
+    /// passed to the `update()` call that produced it.
+    ///
+    /// Returns `Some(now)` for `Immediate`, `Some(now + d)` for `Delay(d)`, and
+    /// `None` for `Idle` or if the deadline cannot be represented by `I`.
+    /// Useful for arming an RTC compare value directly, and for merging the
+    /// schedules of several buttons updated at different instants.
+    pub fn deadline<I: TimeInstant<Duration = D>>(self, now: I) -> Option<I> {
+        match self {
+            ServiceTiming::Immediate => Some(now),
+            ServiceTiming::Delay(d) => now.checked_add(d),
+            ServiceTiming::Idle => None,
+        }
+    }
+
     /// Returns the sooner of two service timings.
     pub fn min(self, other: Self) -> Self {
         match (self, other) {
@@ -0,0 +1,134 @@
+//! Deterministic simulation harness.
+//!
+//! Drives a [`ButtHead`] through a scripted input timeline on a virtual
+//! millisecond clock, calling `update()` exactly when the input changes or the
+//! returned [`ServiceTiming`](crate::ServiceTiming) asks for it — the way
+//! well-behaved firmware would. Useful for unit-testing gesture logic built on
+//! top of this crate without real hardware or hand-stepped timestamps.
+
+use alloc::vec::Vec;
+
+use crate::driver;
+use crate::{ButtHead, Config, Event, TimeDuration, TimeInstant};
+
+/// How long the simulation keeps running after the last input change when the
+/// timeline has no explicit end (see [`Timeline::until`]).
+pub const DEFAULT_TAIL_MS: u64 = 10_000;
+
+/// A virtual duration in milliseconds.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimDuration(pub u64);
+
+impl TimeDuration for SimDuration {
+    const ZERO: Self = SimDuration(0);
+
+    fn as_millis(&self) -> u64 {
+        self.0
+    }
+
+    fn from_millis(millis: u64) -> Self {
+        SimDuration(millis)
+    }
+
+    fn saturating_sub(self, other: Self) -> Self {
+        SimDuration(self.0.saturating_sub(other.0))
+    }
+}
+
+/// A virtual instant, in milliseconds since the start of the simulation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimInstant(pub u64);
+
+impl TimeInstant for SimInstant {
+    type Duration = SimDuration;
+
+    fn duration_since(&self, earlier: Self) -> SimDuration {
+        SimDuration(self.0.saturating_sub(earlier.0))
+    }
+
+    fn checked_add(self, duration: SimDuration) -> Option<Self> {
+        self.0.checked_add(duration.0).map(SimInstant)
+    }
+
+    fn checked_sub(self, duration: SimDuration) -> Option<Self> {
+        self.0.checked_sub(duration.0).map(SimInstant)
+    }
+
+    fn checked_duration_since(&self, earlier: Self) -> Option<SimDuration> {
+        self.0.checked_sub(earlier.0).map(SimDuration)
+    }
+}
+
+/// An event produced by a simulation.
+pub type SimEvent = Event<SimDuration, SimInstant>;
+
+/// A scripted sequence of input changes.
+///
+/// Times are absolute milliseconds on the virtual clock. Levels are logical
+/// (pressed = `true`), before any `active_low` inversion:
+/// `Timeline::new().press(0).release(80).press(150).release(200)`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Timeline {
+    edges: Vec<(u64, bool)>,
+    end: Option<u64>,
+}
+
+impl Timeline {
+    /// Creates an empty timeline; the button starts released at t = 0.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Presses the button at `at_ms`.
+    pub fn press(mut self, at_ms: u64) -> Self {
+        self.edges.push((at_ms, true));
+        self
+    }
+
+    /// Releases the button at `at_ms`.
+    pub fn release(mut self, at_ms: u64) -> Self {
+        self.edges.push((at_ms, false));
+        self
+    }
+
+    /// Presses at `at_ms` and releases `duration_ms` later.
+    pub fn click(self, at_ms: u64, duration_ms: u64) -> Self {
+        self.press(at_ms).release(at_ms + duration_ms)
+    }
+
+    /// Stops the simulation at `end_ms`, no matter what is still scheduled.
+    /// Without it, the simulation stops once the button is idle after the last
+    /// input change, or [`DEFAULT_TAIL_MS`] after it.
+    pub fn until(mut self, end_ms: u64) -> Self {
+        self.end = Some(end_ms);
+        self
+    }
+
+    /// Runs the timeline through a fresh [`ButtHead`] and returns every event
+    /// with the instant of the `update()` call that produced it.
+    pub fn run(&self, config: &'static Config<SimDuration>) -> Vec<(SimInstant, SimEvent)> {
+        self.run_on(&mut ButtHead::new(config))
+    }
+
+    /// Like [`Timeline::run`], but drives an existing `button`.
+    pub fn run_on(&self, button: &mut ButtHead<SimInstant>) -> Vec<(SimInstant, SimEvent)> {
+        let mut edges = self.edges.clone();
+        edges.sort_by_key(|&(at, _)| at);
+        let active_low = button.config().active_low;
+
+        let mut events = Vec::new();
+        driver::drive(
+            button,
+            SimInstant(0),
+            edges
+                .into_iter()
+                .map(|(at, pressed)| (at, pressed != active_low)),
+            self.end,
+            DEFAULT_TAIL_MS,
+            |at, event| events.push((at, event)),
+        );
+        events
+    }
+}
@@ -0,0 +1,258 @@
+use crate::state_machine::State;
+use crate::{TimeDuration, TimeInstant};
+
+/// Size in bytes of a [`ButtHead`](crate::ButtHead) snapshot.
+///
+/// Small enough to fit in four 32-bit backup registers.
+pub const SNAPSHOT_LEN: usize = 16;
+
+/// Version of the snapshot format produced by this crate.
+pub const SNAPSHOT_VERSION: u8 = 1;
+
+// Byte layout (multi-byte fields little-endian):
+//
+//   0      version
+//   1      state tag (bits 0..=3) | disabled (bit 6) | input level (bit 7)
+//   2      click count
+//   3      CRC-8 over all other bytes
+//   4..8   hold level
+//   8..12  age of the state's reference instant, in ms
+//   12..16 offset of the next hold deadline from the press instant, in ms
+//          (`NO_HOLD` if no further hold is scheduled); for a pending press,
+//          time from the previous release to the press (`NO_HOLD` if none); for
+//          a confirm press, offset of the next progress deadline; for a
+//          cool-down, time from the release to a press made since
+const TAG_IDLE: u8 = 0;
+const TAG_PRESSED: u8 = 1;
+const TAG_WAIT_FOR_MULTI_CLICK: u8 = 2;
+const TAG_STUCK: u8 = 3;
+const TAG_SUPPRESSED: u8 = 4;
+const TAG_PENDING_PRESS: u8 = 5;
+const TAG_CONFIRMING: u8 = 6;
+const TAG_CONFIRMED: u8 = 7;
+const TAG_COOLDOWN: u8 = 8;
+
+const NO_HOLD: u32 = u32::MAX;
+
+const TAG_MASK: u8 = 0x0F;
+const DISABLED_BIT: u8 = 0x40;
+const INPUT_BIT: u8 = 0x80;
+
+/// Error returned when a snapshot cannot be restored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RestoreError {
+    /// The snapshot was written by an incompatible format version (or the
+    /// storage was never written, e.g. cleared backup registers).
+    UnsupportedVersion(u8),
+
+    /// The checksum does not match; the snapshot is corrupt.
+    ChecksumMismatch,
+
+    /// The snapshot decodes to a state the state machine cannot be in.
+    InvalidState,
+}
+
+/// Encodes `state`, the debounced input level and whether the button is
+/// enabled into a snapshot. Instants are stored as ages relative to `now`.
+pub(crate) fn encode<I: TimeInstant>(
+    state: &State<I>,
+    input: bool,
+    enabled: bool,
+    now: I,
+) -> [u8; SNAPSHOT_LEN] {
+    let (tag, click_count, hold_level, age, hold_offset) = match *state {
+        State::Idle => (TAG_IDLE, 0, 0, 0, 0),
+        State::PendingPress {
+            pressed_at,
+            click_count,
+            released_at,
+        } => (
+            TAG_PENDING_PRESS,
+            click_count,
+            0,
+            millis(now.duration_since(pressed_at)),
+            released_at.map_or(NO_HOLD, |at| millis(pressed_at.duration_since(at))),
+        ),
+        State::Pressed {
+            pressed_at,
+            next_hold_at,
+            click_count,
+            hold_level,
+        } => (
+            TAG_PRESSED,
+            click_count,
+            hold_level,
+            millis(now.duration_since(pressed_at)),
+            next_hold_at.map_or(NO_HOLD, |at| millis(at.duration_since(pressed_at))),
+        ),
+        State::WaitForMultiClick {
+            click_count,
+            released_at,
+        } => (
+            TAG_WAIT_FOR_MULTI_CLICK,
+            click_count,
+            0,
+            millis(now.duration_since(released_at)),
+            0,
+        ),
+        State::Stuck { pressed_at } => (TAG_STUCK, 0, 0, millis(now.duration_since(pressed_at)), 0),
+        State::Suppressed { pressed_at } => (
+            TAG_SUPPRESSED,
+            0,
+            0,
+            millis(now.duration_since(pressed_at)),
+            0,
+        ),
+        State::Confirming {
+            pressed_at,
+            next_progress_at,
+        } => (
+            TAG_CONFIRMING,
+            0,
+            0,
+            millis(now.duration_since(pressed_at)),
+            next_progress_at.map_or(NO_HOLD, |at| millis(at.duration_since(pressed_at))),
+        ),
+        State::Confirmed { pressed_at } => (
+            TAG_CONFIRMED,
+            0,
+            0,
+            millis(now.duration_since(pressed_at)),
+            0,
+        ),
+        State::Cooldown {
+            released_at,
+            pressed_at,
+        } => (
+            TAG_COOLDOWN,
+            0,
+            0,
+            millis(now.duration_since(released_at)),
+            pressed_at.map_or(NO_HOLD, |at| millis(at.duration_since(released_at))),
+        ),
+    };
+
+    let mut bytes = [0u8; SNAPSHOT_LEN];
+    bytes[0] = SNAPSHOT_VERSION;
+    bytes[1] = tag | if input { INPUT_BIT } else { 0 } | if enabled { 0 } else { DISABLED_BIT };
+    bytes[2] = click_count;
+    bytes[4..8].copy_from_slice(&hold_level.to_le_bytes());
+    bytes[8..12].copy_from_slice(&age.to_le_bytes());
+    bytes[12..16].copy_from_slice(&hold_offset.to_le_bytes());
+    bytes[3] = checksum(&bytes);
+    bytes
+}
+
+/// Decodes a snapshot into a state, the debounced input level and whether the
+/// button is enabled.
+///
+/// `elapsed` is the time that passed between taking the snapshot and `now`.
+/// Reference instants that would lie before the start of the clock are
+/// clamped to `now`.
+pub(crate) fn decode<I: TimeInstant>(
+    bytes: &[u8; SNAPSHOT_LEN],
+    now: I,
+    elapsed: I::Duration,
+) -> Result<(State<I>, bool, bool), RestoreError> {
+    if bytes[0] != SNAPSHOT_VERSION {
+        return Err(RestoreError::UnsupportedVersion(bytes[0]));
+    }
+    if bytes[3] != checksum(bytes) {
+        return Err(RestoreError::ChecksumMismatch);
+    }
+    if bytes[1] & !(TAG_MASK | DISABLED_BIT | INPUT_BIT) != 0 {
+        return Err(RestoreError::InvalidState);
+    }
+
+    let input = bytes[1] & INPUT_BIT != 0;
+    let enabled = bytes[1] & DISABLED_BIT == 0;
+    let click_count = bytes[2];
+    let hold_level = u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+    let age = u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]);
+    let hold_offset = u32::from_le_bytes([bytes[12], bytes[13], bytes[14], bytes[15]]);
+
+    let age = I::Duration::from_millis(u64::from(age).saturating_add(elapsed.as_millis()));
+    let at = now.checked_sub(age).unwrap_or(now);
+
+    let state = match (bytes[1] & TAG_MASK, input) {
+        (TAG_IDLE, false) => State::Idle,
+        // A disabled button keeps tracking the level without a gesture.
+        (TAG_IDLE, true) if !enabled => State::Idle,
+        (TAG_PENDING_PRESS, true) => State::PendingPress {
+            pressed_at: at,
+            click_count,
+            released_at: match hold_offset {
+                NO_HOLD => None,
+                offset => Some(
+                    at.checked_sub(I::Duration::from_millis(offset as u64))
+                        .unwrap_or(at),
+                ),
+            },
+        },
+        (TAG_PRESSED, true) => {
+            let next_hold_at = match hold_offset {
+                NO_HOLD => None,
+                offset => at.checked_add(I::Duration::from_millis(offset as u64)),
+            };
+            State::Pressed {
+                pressed_at: at,
+                next_hold_at,
+                click_count,
+                hold_level,
+            }
+        }
+        (TAG_WAIT_FOR_MULTI_CLICK, false) if click_count > 0 => State::WaitForMultiClick {
+            click_count,
+            released_at: at,
+        },
+        (TAG_STUCK, true) => State::Stuck { pressed_at: at },
+        (TAG_SUPPRESSED, true) => State::Suppressed { pressed_at: at },
+        (TAG_CONFIRMING, true) => State::Confirming {
+            pressed_at: at,
+            next_progress_at: match hold_offset {
+                NO_HOLD => None,
+                offset => at.checked_add(I::Duration::from_millis(offset as u64)),
+            },
+        },
+        (TAG_CONFIRMED, true) => State::Confirmed { pressed_at: at },
+        // The input level must agree with whether a press was recorded.
+        (TAG_COOLDOWN, input) if input == (hold_offset != NO_HOLD) => State::Cooldown {
+            released_at: at,
+            pressed_at: input.then(|| {
+                at.checked_add(I::Duration::from_millis(hold_offset as u64))
+                    .unwrap_or(at)
+            }),
+        },
+        _ => return Err(RestoreError::InvalidState),
+    };
+    // A disabled button tracks no gesture.
+    if !enabled && !matches!(state, State::Idle) {
+        return Err(RestoreError::InvalidState);
+    }
+
+    Ok((state, input, enabled))
+}
+
+fn millis<D: TimeDuration>(d: D) -> u32 {
+    u32::try_from(d.as_millis()).unwrap_or(u32::MAX)
+}
+
+/// CRC-8 (polynomial 0x07) over every byte except the checksum byte itself.
+fn checksum(bytes: &[u8; SNAPSHOT_LEN]) -> u8 {
+    let mut crc = 0u8;
+    for (i, &byte) in bytes.iter().enumerate() {
+        if i == 3 {
+            continue;
+        }
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x07
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
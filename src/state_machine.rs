@@ -1,4 +1,7 @@
-use crate::{Config, Event, ServiceTiming, TimeDuration, TimeInstant};
+use crate::{
+    ButtonState, Config, Event, HoldProgress, ServiceTiming, TimeDuration, TimeFaultPolicy,
+    TimeInstant, UpdateError,
+};
 
 type UpdateResult<I> = (
     Option<Event<<I as TimeInstant>::Duration, I>>,
@@ -12,42 +15,270 @@ pub(crate) enum Edge {
 }
 
 #[derive(Debug, Clone, Copy)]
-enum State<I: TimeInstant> {
+pub(crate) enum State<I: TimeInstant> {
     Idle,
+    /// Pressed, but not yet for `min_press_duration`. `released_at` is the
+    /// release that ended the previous click, if any, so a rejected press can
+    /// resume waiting for the next one.
+    PendingPress {
+        pressed_at: I,
+        click_count: u8,
+        released_at: Option<I>,
+    },
     Pressed {
         pressed_at: I,
-        next_hold_at: I,
+        /// `None` if there is no further hold stage, or the next hold deadline
+        /// lies beyond the range of `I`.
+        next_hold_at: Option<I>,
         click_count: u8,
-        hold_level: u8,
+        hold_level: u32,
     },
     WaitForMultiClick {
         click_count: u8,
         released_at: I,
     },
+    Stuck {
+        pressed_at: I,
+    },
+    Suppressed {
+        pressed_at: I,
+    },
+    /// Confirm mode: held, but not yet for `confirm_duration`.
+    Confirming {
+        pressed_at: I,
+        /// `None` if no further `ConfirmProgress` is due before `Confirmed`.
+        next_progress_at: Option<I>,
+    },
+    /// Confirm mode: `Confirmed` fired; waiting for the release.
+    Confirmed {
+        pressed_at: I,
+    },
+    /// Confirm mode: a confirmed press was released at `released_at`.
+    /// `pressed_at` is a press made during the cool-down, which is ignored.
+    Cooldown {
+        released_at: I,
+        pressed_at: Option<I>,
+    },
 }
 
 pub(crate) struct StateMachine<I: TimeInstant> {
     state: State<I>,
+    /// An event produced by the previous update that could not be returned
+    /// alongside the event that update already carried. Emitted first on the
+    /// next update.
+    pending: Option<Event<I::Duration, I>>,
     config: &'static Config<I::Duration>,
 }
 
 impl<I: TimeInstant> StateMachine<I> {
     pub fn new(config: &'static Config<I::Duration>) -> Self {
+        Self::with_state(config, State::Idle)
+    }
+
+    /// Creates a state machine resuming from `state`, e.g. one decoded from a
+    /// snapshot.
+    pub fn with_state(config: &'static Config<I::Duration>, state: State<I>) -> Self {
         Self {
-            state: State::Idle,
+            state,
+            pending: None,
             config,
         }
     }
 
-    /// Returns the instant the button was pressed if currently in the `Pressed`
-    /// state, or `None` otherwise.
+    /// Creates a state machine that has already seen a press at `pressed_at`.
+    /// The resulting `Press` event is queued and returned by the first update.
+    pub fn pressed_since(config: &'static Config<I::Duration>, pressed_at: I) -> Self {
+        let mut state_machine = Self::new(config);
+        if let Ok((event, _)) =
+            state_machine.step(Some(Edge::Press), pressed_at, TimeFaultPolicy::Clamp)
+        {
+            state_machine.pending = event;
+        }
+        state_machine
+    }
+
+    /// Returns the internal state, e.g. for encoding into a snapshot.
+    pub fn raw_state(&self) -> State<I> {
+        self.state
+    }
+
+    /// Returns the instant the button was pressed if currently in the
+    /// `Pressed`, `Stuck` or `Suppressed` state, or `None` otherwise.
     pub fn pressed_at(&self) -> Option<I> {
         match self.state {
-            State::Pressed { pressed_at, .. } => Some(pressed_at),
+            State::PendingPress { pressed_at, .. }
+            | State::Pressed { pressed_at, .. }
+            | State::Stuck { pressed_at }
+            | State::Suppressed { pressed_at }
+            | State::Confirming { pressed_at, .. }
+            | State::Confirmed { pressed_at } => Some(pressed_at),
+            State::Cooldown { pressed_at, .. } => pressed_at,
             _ => None,
         }
     }
 
+    /// Returns a public view of the current state.
+    pub fn state(&self) -> ButtonState<I> {
+        match self.state {
+            State::Idle => ButtonState::Idle,
+            State::PendingPress {
+                pressed_at,
+                click_count,
+                ..
+            } => ButtonState::PendingPress {
+                since: pressed_at,
+                clicks: click_count,
+            },
+            State::Pressed {
+                pressed_at,
+                click_count,
+                hold_level,
+                ..
+            } => ButtonState::Pressed {
+                since: pressed_at,
+                clicks: click_count,
+                hold_level,
+            },
+            State::WaitForMultiClick {
+                click_count,
+                released_at,
+            } => ButtonState::WaitForMultiClick {
+                clicks: click_count,
+                since: released_at,
+            },
+            State::Stuck { pressed_at } => ButtonState::Stuck { since: pressed_at },
+            State::Suppressed { pressed_at } => ButtonState::Suppressed { since: pressed_at },
+            State::Confirming { pressed_at, .. } => ButtonState::Confirming { since: pressed_at },
+            State::Confirmed { pressed_at } => ButtonState::Confirmed { since: pressed_at },
+            State::Cooldown { released_at, .. } => ButtonState::Cooldown { since: released_at },
+        }
+    }
+
+    /// Returns how long until the state machine can next produce an event
+    /// without an input change, or `None` if nothing is scheduled.
+    pub fn time_until_next_event(&self, now: I) -> Option<I::Duration> {
+        if self.pending.is_some() {
+            return Some(I::Duration::ZERO);
+        }
+        match self.state {
+            State::Idle
+            | State::Stuck { .. }
+            | State::Suppressed { .. }
+            | State::Confirmed { .. } => None,
+            State::Confirming {
+                pressed_at,
+                next_progress_at,
+            } => match self.confirming_timing(pressed_at, next_progress_at, now) {
+                ServiceTiming::Delay(d) => Some(d),
+                ServiceTiming::Immediate => Some(I::Duration::ZERO),
+                ServiceTiming::Idle => None,
+            },
+            State::Cooldown { released_at, .. } => {
+                let elapsed = now.duration_since(released_at);
+                Some(self.cooldown().saturating_sub(elapsed))
+            }
+            State::PendingPress { pressed_at, .. } => {
+                let elapsed = now.duration_since(pressed_at);
+                Some(self.min_press().saturating_sub(elapsed))
+            }
+            State::Pressed {
+                pressed_at,
+                next_hold_at,
+                ..
+            } => {
+                let elapsed = now.duration_since(pressed_at);
+                let hold_remaining =
+                    next_hold_at.map(|at| at.duration_since(pressed_at).saturating_sub(elapsed));
+                match self.pressed_timing(elapsed, hold_remaining) {
+                    ServiceTiming::Delay(d) => Some(d),
+                    ServiceTiming::Immediate => Some(I::Duration::ZERO),
+                    ServiceTiming::Idle => None,
+                }
+            }
+            State::WaitForMultiClick {
+                click_count,
+                released_at,
+            } => {
+                let at_max = self
+                    .config
+                    .max_click_count
+                    .is_some_and(|max| click_count >= max);
+                if at_max {
+                    Some(I::Duration::ZERO)
+                } else {
+                    let elapsed = now.duration_since(released_at);
+                    Some(self.config.click_timeout.saturating_sub(elapsed))
+                }
+            }
+        }
+    }
+
+    /// Returns the progress toward the next `Hold` or `HoldStage`, or toward
+    /// `Confirmed` in confirm mode. Returns `None` if the state machine is not
+    /// in `Pressed` or `Confirming`, or no further hold is scheduled.
+    pub fn hold_progress(&self, now: I) -> Option<HoldProgress<I::Duration>> {
+        let (pressed_at, total) = match self.state {
+            State::Pressed {
+                pressed_at,
+                next_hold_at: Some(next_hold_at),
+                ..
+            } => (pressed_at, next_hold_at.duration_since(pressed_at)),
+            State::Confirming { pressed_at, .. } => (pressed_at, self.confirm_duration()?),
+            _ => return None,
+        };
+        let elapsed = now.duration_since(pressed_at);
+        let elapsed = if elapsed.as_millis() > total.as_millis() {
+            total
+        } else {
+            elapsed
+        };
+        Some(HoldProgress { elapsed, total })
+    }
+
+    /// Returns `true` if the button exceeded `max_press_duration` and is
+    /// quarantined until it is released.
+    pub fn is_stuck(&self) -> bool {
+        matches!(self.state, State::Stuck { .. })
+    }
+
+    /// Returns the service timing for a press that has lasted `elapsed` and is
+    /// next due for a `Hold` after `hold_remaining` (`None` if no further hold
+    /// is scheduled), taking the stuck deadline into account.
+    fn pressed_timing(
+        &self,
+        elapsed: I::Duration,
+        hold_remaining: Option<I::Duration>,
+    ) -> ServiceTiming<I::Duration> {
+        let hold = hold_remaining.map_or(ServiceTiming::Idle, ServiceTiming::Delay);
+        match self.config.max_press_duration {
+            Some(max) => hold.min(ServiceTiming::Delay(max.saturating_sub(elapsed))),
+            None => hold,
+        }
+    }
+
+    /// Returns the service timing for a confirm press that began at
+    /// `pressed_at` and is next due for a `ConfirmProgress` at
+    /// `next_progress_at`.
+    fn confirming_timing(
+        &self,
+        pressed_at: I,
+        next_progress_at: Option<I>,
+        now: I,
+    ) -> ServiceTiming<I::Duration> {
+        let elapsed = now.duration_since(pressed_at);
+        let confirm = self
+            .confirm_duration()
+            .map_or(ServiceTiming::Idle, |total| {
+                ServiceTiming::Delay(total.saturating_sub(elapsed))
+            });
+        match next_progress_at {
+            Some(at) => confirm.min(ServiceTiming::Delay(
+                at.duration_since(pressed_at).saturating_sub(elapsed),
+            )),
+            None => confirm,
+        }
+    }
+
     /// Cancels a pending `Click` event by resetting from `WaitForMultiClick`
     /// back to `Idle`. Returns `true` if a pending click was cancelled, `false`
     /// if the state machine was not in `WaitForMultiClick`.
@@ -64,25 +295,248 @@ impl<I: TimeInstant> StateMachine<I> {
         }
     }
 
-    pub fn update(&mut self, edge: Option<Edge>, now: I) -> UpdateResult<I> {
-        match self.state {
-            State::Idle => match edge {
-                Some(Edge::Press) => {
-                    let next_hold_at = now.checked_add(self.config.hold_delay).unwrap_or(now);
-                    self.state = State::Pressed {
-                        pressed_at: now,
-                        next_hold_at,
-                        click_count: 0,
-                        hold_level: 0,
-                    };
-                    (
-                        Some(Event::Press { at: now }),
-                        ServiceTiming::Delay(self.config.hold_delay),
-                    )
+    /// Ignores the rest of the current press: no further `Hold` or `Stuck`
+    /// fires, and the release emits nothing. Clicks that preceded the press
+    /// are dropped. Returns `true` if a press was suppressed, `false` if the
+    /// state machine was not in `PendingPress`, `Pressed` or `Confirming`.
+    pub fn suppress_until_release(&mut self) -> bool {
+        if let State::PendingPress { pressed_at, .. }
+        | State::Pressed { pressed_at, .. }
+        | State::Confirming { pressed_at, .. } = self.state
+        {
+            self.state = State::Suppressed { pressed_at };
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Cancels whatever gesture is in progress: a press is suppressed until
+    /// release, a pending `Click` is dropped. Returns `true` if anything was
+    /// cancelled.
+    pub fn cancel_gesture(&mut self) -> bool {
+        self.suppress_until_release() || self.cancel_pending_click()
+    }
+
+    /// Advances the state machine. If a deadline overflows `I`, `policy`
+    /// decides between scheduling no further `Hold` (`Clamp`) and failing with
+    /// the state left untouched (anything else).
+    pub fn update(
+        &mut self,
+        edge: Option<Edge>,
+        now: I,
+        policy: TimeFaultPolicy,
+    ) -> Result<UpdateResult<I>, UpdateError> {
+        // A queued event goes out first. An edge arriving in the same call is
+        // still processed now (so its timestamp is exact), and its event is
+        // queued in turn.
+        if let Some(pending) = self.pending.take() {
+            if edge.is_some() {
+                match self.step(edge, now, policy) {
+                    Ok((event, _)) => self.pending = event,
+                    Err(error) => {
+                        self.pending = Some(pending);
+                        return Err(error);
+                    }
                 }
+            }
+            let timing = if self.pending.is_none() && matches!(self.state, State::Idle) {
+                ServiceTiming::Idle
+            } else {
+                ServiceTiming::Immediate
+            };
+            return Ok((Some(pending), timing));
+        }
+
+        self.step(edge, now, policy)
+    }
+
+    /// Returns `from + delay`, or `None` under `TimeFaultPolicy::Clamp` if that
+    /// instant cannot be represented: a deadline past the end of the clock can
+    /// never be reached, so no event is scheduled for it.
+    fn deadline(
+        from: I,
+        delay: I::Duration,
+        policy: TimeFaultPolicy,
+    ) -> Result<Option<I>, UpdateError> {
+        match from.checked_add(delay) {
+            Some(at) => Ok(Some(at)),
+            None if policy == TimeFaultPolicy::Clamp => Ok(None),
+            None => Err(UpdateError::DeadlineOverflow),
+        }
+    }
+
+    /// Returns the configured minimum press duration (zero if unset).
+    fn min_press(&self) -> I::Duration {
+        self.config.min_press_duration.unwrap_or(I::Duration::ZERO)
+    }
+
+    /// Returns the configured confirm duration, if confirm mode is enabled.
+    fn confirm_duration(&self) -> Option<I::Duration> {
+        self.config.confirm_duration
+    }
+
+    /// Returns the configured confirm cool-down (zero if unset).
+    fn cooldown(&self) -> I::Duration {
+        self.config.confirm_cooldown.unwrap_or(I::Duration::ZERO)
+    }
+
+    /// Returns the first `ConfirmProgress` deadline after `from`, or `None` if
+    /// progress events are disabled or the next one would not fire before
+    /// `Confirmed`.
+    fn next_progress(
+        &self,
+        pressed_at: I,
+        from: I,
+        policy: TimeFaultPolicy,
+    ) -> Result<Option<I>, UpdateError> {
+        let (Some(total), Some(interval)) = (
+            self.confirm_duration(),
+            self.config.confirm_progress_interval,
+        ) else {
+            return Ok(None);
+        };
+        if interval.as_millis() == 0 {
+            return Ok(None);
+        }
+        let offset = from
+            .duration_since(pressed_at)
+            .as_millis()
+            .saturating_add(interval.as_millis());
+        if offset >= total.as_millis() {
+            return Ok(None);
+        }
+        Self::deadline(from, interval, policy)
+    }
+
+    /// Starts a press at `now`, preceded by `click_count` clicks of which the
+    /// last was released at `released_at`.
+    fn begin_press(
+        &mut self,
+        now: I,
+        click_count: u8,
+        released_at: Option<I>,
+        policy: TimeFaultPolicy,
+    ) -> Result<UpdateResult<I>, UpdateError> {
+        let min = self.min_press();
+        if min.as_millis() > 0 {
+            self.state = State::PendingPress {
+                pressed_at: now,
+                click_count,
+                released_at,
+            };
+            return Ok((None, ServiceTiming::Delay(min)));
+        }
+        self.confirm_press(now, now, click_count, policy)
+    }
+
+    /// Enters `Pressed` for a press that began at `pressed_at` and emits its
+    /// `Press` event, or `Confirming` and `ConfirmStarted` in confirm mode.
+    fn confirm_press(
+        &mut self,
+        pressed_at: I,
+        now: I,
+        click_count: u8,
+        policy: TimeFaultPolicy,
+    ) -> Result<UpdateResult<I>, UpdateError> {
+        if self.confirm_duration().is_some() {
+            let next_progress_at = self.next_progress(pressed_at, pressed_at, policy)?;
+            self.state = State::Confirming {
+                pressed_at,
+                next_progress_at,
+            };
+            return Ok((
+                Some(Event::ConfirmStarted { at: pressed_at }),
+                self.confirming_timing(pressed_at, next_progress_at, now),
+            ));
+        }
+
+        let hold_delay = self
+            .config
+            .hold_stages
+            .first()
+            .copied()
+            .unwrap_or(self.config.hold_delay);
+        let next_hold_at = Self::deadline(pressed_at, hold_delay, policy)?;
+        self.state = State::Pressed {
+            pressed_at,
+            next_hold_at,
+            click_count,
+            hold_level: 0,
+        };
+        let elapsed = now.duration_since(pressed_at);
+        Ok((
+            Some(Event::Press { at: pressed_at }),
+            self.pressed_timing(
+                elapsed,
+                next_hold_at.map(|_| hold_delay.saturating_sub(elapsed)),
+            ),
+        ))
+    }
+
+    fn step(
+        &mut self,
+        edge: Option<Edge>,
+        now: I,
+        policy: TimeFaultPolicy,
+    ) -> Result<UpdateResult<I>, UpdateError> {
+        Ok(match self.state {
+            State::Idle => match edge {
+                Some(Edge::Press) => self.begin_press(now, 0, None, policy)?,
                 _ => (None, ServiceTiming::Idle),
             },
 
+            State::PendingPress {
+                pressed_at,
+                click_count,
+                released_at,
+            } => {
+                let elapsed = now.duration_since(pressed_at);
+                let long_enough = elapsed.as_millis() >= self.min_press().as_millis();
+                match edge {
+                    // Too short — reject it and resume where the previous
+                    // click left off.
+                    Some(Edge::Release) if !long_enough => {
+                        let timing = match released_at {
+                            Some(released_at) => {
+                                self.state = State::WaitForMultiClick {
+                                    click_count,
+                                    released_at,
+                                };
+                                ServiceTiming::Delay(
+                                    self.config
+                                        .click_timeout
+                                        .saturating_sub(now.duration_since(released_at)),
+                                )
+                            }
+                            None => {
+                                self.state = State::Idle;
+                                ServiceTiming::Idle
+                            }
+                        };
+                        let event = self
+                            .config
+                            .report_glitches
+                            .then_some(Event::Glitch { duration: elapsed });
+                        (event, timing)
+                    }
+                    // Serviced late: the press was real. Report it, and queue
+                    // the release's event for the next call.
+                    Some(Edge::Release) => {
+                        let (press, _) =
+                            self.confirm_press(pressed_at, now, click_count, policy)?;
+                        let (release, _) = self.step(Some(Edge::Release), now, policy)?;
+                        self.pending = release;
+                        (press, ServiceTiming::Immediate)
+                    }
+                    _ if long_enough => self.confirm_press(pressed_at, now, click_count, policy)?,
+                    _ => (
+                        None,
+                        ServiceTiming::Delay(self.min_press().saturating_sub(elapsed)),
+                    ),
+                }
+            }
+
             State::Pressed {
                 pressed_at,
                 next_hold_at,
@@ -92,14 +546,20 @@ impl<I: TimeInstant> StateMachine<I> {
                 Some(Edge::Release) => {
                     let duration = now.duration_since(pressed_at);
                     if hold_level > 0 {
-                        // A hold was emitted — this is not a click.
+                        // A hold was emitted — this is not a click. The hold
+                        // gesture's end is reported on the next call.
                         self.state = State::Idle;
+                        self.pending = Some(Event::HoldEnd {
+                            clicks_before: click_count,
+                            levels: hold_level,
+                            duration,
+                        });
                         (
                             Some(Event::Release {
                                 duration,
                                 click_follows: false,
                             }),
-                            ServiceTiming::Idle,
+                            ServiceTiming::Immediate,
                         )
                     } else {
                         let new_count = click_count.saturating_add(1);
@@ -128,28 +588,65 @@ impl<I: TimeInstant> StateMachine<I> {
                     }
                 }
                 _ => {
-                    // No edge — check if the hold deadline has been reached.
                     let elapsed = now.duration_since(pressed_at);
+
+                    // Pressed for too long — assume a fault and quarantine the
+                    // button until it is physically released.
+                    if let Some(max) = self.config.max_press_duration
+                        && elapsed.as_millis() >= max.as_millis()
+                    {
+                        self.state = State::Stuck { pressed_at };
+                        return Ok((
+                            Some(Event::Stuck { duration: elapsed }),
+                            ServiceTiming::Idle,
+                        ));
+                    }
+
+                    // No edge — check if the hold deadline has been reached.
+                    let Some(next_hold_at) = next_hold_at else {
+                        return Ok((None, self.pressed_timing(elapsed, None)));
+                    };
                     let hold_elapsed = next_hold_at.duration_since(pressed_at);
 
                     if elapsed.as_millis() >= hold_elapsed.as_millis() {
-                        let event = Event::Hold {
-                            clicks_before: click_count,
-                            level: hold_level,
+                        let stages = self.config.hold_stages;
+                        let (event, new_next_hold_at, interval) = if stages.is_empty() {
+                            let new_next_hold_at =
+                                Self::deadline(next_hold_at, self.config.hold_interval, policy)?;
+                            let event = Event::Hold {
+                                clicks_before: click_count,
+                                level: hold_level,
+                            };
+                            (event, new_next_hold_at, self.config.hold_interval)
+                        } else {
+                            // Stages are offsets from the press, so schedule
+                            // straight to the next one.
+                            let next_stage = stages.get(hold_level as usize + 1).copied();
+                            let new_next_hold_at = match next_stage {
+                                Some(offset) => Self::deadline(pressed_at, offset, policy)?,
+                                None => None,
+                            };
+                            let event = Event::HoldStage {
+                                index: u8::try_from(hold_level).unwrap_or(u8::MAX),
+                                clicks_before: click_count,
+                            };
+                            let remaining = next_stage
+                                .map_or(I::Duration::ZERO, |offset| offset.saturating_sub(elapsed));
+                            (event, new_next_hold_at, remaining)
                         };
-                        let new_next_hold_at = next_hold_at
-                            .checked_add(self.config.hold_interval)
-                            .unwrap_or(next_hold_at);
                         self.state = State::Pressed {
                             pressed_at,
                             next_hold_at: new_next_hold_at,
                             click_count,
                             hold_level: hold_level.saturating_add(1),
                         };
-                        (Some(event), ServiceTiming::Delay(self.config.hold_interval))
+                        (
+                            Some(event),
+                            self.pressed_timing(elapsed, new_next_hold_at.map(|_| interval)),
+                        )
                     } else {
                         let remaining = hold_elapsed.saturating_sub(elapsed);
-                        (None, ServiceTiming::Delay(remaining))
+                        (None, self.pressed_timing(elapsed, Some(remaining)))
                     }
                 }
             },
@@ -159,17 +656,7 @@ impl<I: TimeInstant> StateMachine<I> {
                 released_at,
             } => match edge {
                 Some(Edge::Press) => {
-                    let next_hold_at = now.checked_add(self.config.hold_delay).unwrap_or(now);
-                    self.state = State::Pressed {
-                        pressed_at: now,
-                        next_hold_at,
-                        click_count,
-                        hold_level: 0,
-                    };
-                    (
-                        Some(Event::Press { at: now }),
-                        ServiceTiming::Delay(self.config.hold_delay),
-                    )
+                    self.begin_press(now, click_count, Some(released_at), policy)?
                 }
                 _ => {
                     // Fire immediately if we've hit max_click_count.
@@ -179,10 +666,10 @@ impl<I: TimeInstant> StateMachine<I> {
                         .is_some_and(|max| click_count >= max);
                     if at_max {
                         self.state = State::Idle;
-                        return (
+                        return Ok((
                             Some(Event::Click { count: click_count }),
                             ServiceTiming::Idle,
-                        );
+                        ));
                     }
 
                     let elapsed = now.duration_since(released_at);
@@ -198,6 +685,108 @@ impl<I: TimeInstant> StateMachine<I> {
                     }
                 }
             },
-        }
+
+            State::Confirming {
+                pressed_at,
+                next_progress_at,
+            } => {
+                let elapsed = now.duration_since(pressed_at);
+                let total = self.confirm_duration().unwrap_or(I::Duration::ZERO);
+                if elapsed.as_millis() >= total.as_millis() {
+                    // Held long enough. A release serviced late still counts
+                    // as confirmed, and starts the cool-down.
+                    self.state = State::Confirmed { pressed_at };
+                    let (_, timing) = match edge {
+                        Some(Edge::Release) => self.step(edge, now, policy)?,
+                        _ => (None, ServiceTiming::Idle),
+                    };
+                    return Ok((Some(Event::Confirmed), timing));
+                }
+                match edge {
+                    Some(Edge::Release) => {
+                        self.state = State::Idle;
+                        (
+                            Some(Event::ConfirmAborted { held_for: elapsed }),
+                            ServiceTiming::Idle,
+                        )
+                    }
+                    _ => match next_progress_at {
+                        Some(at)
+                            if elapsed.as_millis() >= at.duration_since(pressed_at).as_millis() =>
+                        {
+                            let next_progress_at = self.next_progress(pressed_at, at, policy)?;
+                            self.state = State::Confirming {
+                                pressed_at,
+                                next_progress_at,
+                            };
+                            (
+                                Some(Event::ConfirmProgress { elapsed }),
+                                self.confirming_timing(pressed_at, next_progress_at, now),
+                            )
+                        }
+                        _ => (
+                            None,
+                            self.confirming_timing(pressed_at, next_progress_at, now),
+                        ),
+                    },
+                }
+            }
+
+            State::Confirmed { .. } => match edge {
+                Some(Edge::Release) if self.cooldown().as_millis() > 0 => {
+                    self.state = State::Cooldown {
+                        released_at: now,
+                        pressed_at: None,
+                    };
+                    (None, ServiceTiming::Delay(self.cooldown()))
+                }
+                Some(Edge::Release) => {
+                    self.state = State::Idle;
+                    (None, ServiceTiming::Idle)
+                }
+                _ => (None, ServiceTiming::Idle),
+            },
+
+            State::Cooldown {
+                released_at,
+                pressed_at,
+            } => {
+                let elapsed = now.duration_since(released_at);
+                let remaining = self.cooldown().saturating_sub(elapsed);
+                if remaining.as_millis() > 0 {
+                    // Still cooling down — track the input, nothing else.
+                    self.state = State::Cooldown {
+                        released_at,
+                        pressed_at: match edge {
+                            Some(Edge::Press) => Some(now),
+                            Some(Edge::Release) => None,
+                            None => pressed_at,
+                        },
+                    };
+                    return Ok((None, ServiceTiming::Delay(remaining)));
+                }
+                match (edge, pressed_at) {
+                    (Some(Edge::Press), _) => self.begin_press(now, 0, None, policy)?,
+                    // A press made during the cool-down must be released first.
+                    (None, Some(pressed_at)) => {
+                        self.state = State::Suppressed { pressed_at };
+                        (None, ServiceTiming::Idle)
+                    }
+                    _ => {
+                        self.state = State::Idle;
+                        (None, ServiceTiming::Idle)
+                    }
+                }
+            }
+
+            State::Stuck { .. } | State::Suppressed { .. } => match edge {
+                // A real release ends the quarantine silently.
+                Some(Edge::Release) => {
+                    self.state = State::Idle;
+                    (None, ServiceTiming::Idle)
+                }
+                _ => (None, ServiceTiming::Idle),
+            },
+        })
     }
 }
@@ -12,4 +12,14 @@ pub trait TimeInstant: Copy + PartialEq {
     fn duration_since(&self, earlier: Self) -> Self::Duration;
     fn checked_add(self, duration: Self::Duration) -> Option<Self>;
     fn checked_sub(self, duration: Self::Duration) -> Option<Self>;
+
+    /// Returns the time elapsed since `earlier`, or `None` if `earlier` is
+    /// later than `self` (the clock went backwards).
+    ///
+    /// The default cannot tell and always returns `Some`. Override it so that
+    /// `ButtHead` can detect a rewinding clock and apply its
+    /// [`TimeFaultPolicy`](crate::TimeFaultPolicy).
+    fn checked_duration_since(&self, earlier: Self) -> Option<Self::Duration> {
+        Some(self.duration_since(earlier))
+    }
 }
@@ -0,0 +1,243 @@
+//! Input trace recording and replay.
+//!
+//! A trace is a sequence of compact [`TraceSample`]s, each holding the raw
+//! input level and the time since the previous sample. [`Recorder`] captures
+//! one on-device into a fixed ring buffer while forwarding to a [`ButtHead`];
+//! [`replay`] feeds a trace back through any [`Config`] to reproduce — or
+//! re-tune — the resulting events.
+
+use crate::{
+    ButtHead, ButtonHandler, Config, Event, ServiceTiming, TimeDuration, TimeInstant, UpdateError,
+    UpdateResult, driver,
+};
+
+/// Size in bytes of an encoded [`TraceSample`].
+pub const SAMPLE_LEN: usize = 2;
+
+/// Longest delta a single sample can hold. Longer gaps are split into
+/// several samples with the same level.
+pub const MAX_DELTA_MS: u16 = 0x7FFF;
+
+const LEVEL_BIT: u16 = 0x8000;
+
+/// One input change: the raw pin level (as passed to `update()`), and the
+/// milliseconds since the previous sample.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TraceSample {
+    /// Milliseconds since the previous sample (0..=`MAX_DELTA_MS`).
+    pub delta_ms: u16,
+    /// Raw pin level.
+    pub level: bool,
+}
+
+impl TraceSample {
+    /// Encodes the sample as two little-endian bytes: the level in the top bit,
+    /// the delta in the lower 15 bits.
+    pub fn to_bytes(self) -> [u8; SAMPLE_LEN] {
+        let level = if self.level { LEVEL_BIT } else { 0 };
+        (level | (self.delta_ms & MAX_DELTA_MS)).to_le_bytes()
+    }
+
+    /// Decodes a sample produced by [`TraceSample::to_bytes`].
+    pub fn from_bytes(bytes: [u8; SAMPLE_LEN]) -> Self {
+        let raw = u16::from_le_bytes(bytes);
+        Self {
+            delta_ms: raw & MAX_DELTA_MS,
+            level: raw & LEVEL_BIT != 0,
+        }
+    }
+}
+
+/// Decodes a byte buffer of concatenated samples. A trailing odd byte is
+/// ignored.
+pub fn decode(bytes: &[u8]) -> impl Iterator<Item = TraceSample> + '_ {
+    bytes
+        .chunks_exact(SAMPLE_LEN)
+        .map(|chunk| TraceSample::from_bytes([chunk[0], chunk[1]]))
+}
+
+/// A [`ButtHead`] wrapper that records every input change into a ring buffer
+/// of `N` samples. When full, the oldest samples are overwritten.
+///
+/// `update`, `try_update` and `update_with` record and forward; everything
+/// else (`cancel_gesture`, `disable`, `snapshot`, ...) is reached through
+/// [`Recorder::button_mut`].
+pub struct Recorder<I: TimeInstant, const N: usize> {
+    button: ButtHead<I>,
+    samples: [TraceSample; N],
+    head: usize,
+    len: usize,
+    last_level: Option<bool>,
+    last_at: Option<I>,
+}
+
+impl<I: TimeInstant, const N: usize> Recorder<I, N> {
+    /// Creates a recorder around a new `ButtHead`.
+    pub fn new(config: &'static Config<I::Duration>) -> Self {
+        Self::wrap(ButtHead::new(config))
+    }
+
+    /// Creates a recorder around an existing button, e.g. one from
+    /// `ButtHead::resume_from_wake` or `ButtHead::restore`.
+    pub fn wrap(button: ButtHead<I>) -> Self {
+        Self {
+            button,
+            samples: [TraceSample {
+                delta_ms: 0,
+                level: false,
+            }; N],
+            head: 0,
+            len: 0,
+            last_level: None,
+            last_at: None,
+        }
+    }
+
+    /// Returns the wrapped button.
+    pub fn button(&self) -> &ButtHead<I> {
+        &self.button
+    }
+
+    /// Returns the wrapped button mutably, for the calls `Recorder` does not
+    /// forward. Input fed to it directly is not recorded.
+    pub fn button_mut(&mut self) -> &mut ButtHead<I> {
+        &mut self.button
+    }
+
+    /// Unwraps the button.
+    pub fn into_inner(self) -> ButtHead<I> {
+        self.button
+    }
+
+    /// Records the input if it changed, then forwards to
+    /// [`ButtHead::update`].
+    pub fn update(&mut self, is_pressed: bool, now: I) -> UpdateResult<I::Duration, I> {
+        self.record(is_pressed, now);
+        self.button.update(is_pressed, now)
+    }
+
+    /// Records the input if it changed, then forwards to
+    /// [`ButtHead::try_update`].
+    pub fn try_update(
+        &mut self,
+        is_pressed: bool,
+        now: I,
+    ) -> Result<UpdateResult<I::Duration, I>, UpdateError> {
+        self.record(is_pressed, now);
+        self.button.try_update(is_pressed, now)
+    }
+
+    /// Records the input if it changed, then forwards to
+    /// [`ButtHead::update_with`].
+    pub fn update_with<H: ButtonHandler<I>>(
+        &mut self,
+        handler: &mut H,
+        is_pressed: bool,
+        now: I,
+    ) -> ServiceTiming<I::Duration> {
+        self.record(is_pressed, now);
+        self.button.update_with(handler, is_pressed, now)
+    }
+
+    fn record(&mut self, is_pressed: bool, now: I) {
+        if self.last_level != Some(is_pressed) {
+            // A rewound clock is recorded as no time passing.
+            let mut delta = self.last_at.map_or(0, |at| {
+                now.checked_duration_since(at)
+                    .map_or(0, |elapsed| elapsed.as_millis())
+            });
+            let previous = self.last_level.unwrap_or(is_pressed);
+            while delta > MAX_DELTA_MS as u64 {
+                self.push(TraceSample {
+                    delta_ms: MAX_DELTA_MS,
+                    level: previous,
+                });
+                delta -= MAX_DELTA_MS as u64;
+            }
+            self.push(TraceSample {
+                delta_ms: delta as u16,
+                level: is_pressed,
+            });
+            self.last_level = Some(is_pressed);
+            self.last_at = Some(now);
+        }
+    }
+
+    /// Returns the number of recorded samples.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if nothing has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the recorded samples, oldest first.
+    pub fn samples(&self) -> impl Iterator<Item = TraceSample> + '_ {
+        let start = (self.head + N - self.len) % N.max(1);
+        (0..self.len).map(move |i| self.samples[(start + i) % N])
+    }
+
+    /// Writes the recorded samples, oldest first, into `out` and returns the
+    /// number of bytes written. Stops early if `out` is too small.
+    pub fn write_bytes(&self, out: &mut [u8]) -> usize {
+        let mut written = 0;
+        for (sample, chunk) in self.samples().zip(out.chunks_exact_mut(SAMPLE_LEN)) {
+            chunk.copy_from_slice(&sample.to_bytes());
+            written += SAMPLE_LEN;
+        }
+        written
+    }
+
+    /// Discards all recorded samples. The next update starts a new trace.
+    pub fn clear(&mut self) {
+        self.head = 0;
+        self.len = 0;
+        self.last_level = None;
+        self.last_at = None;
+    }
+
+    fn push(&mut self, sample: TraceSample) {
+        if N == 0 {
+            return;
+        }
+        self.samples[self.head] = sample;
+        self.head = (self.head + 1) % N;
+        self.len = (self.len + 1).min(N);
+    }
+}
+
+/// Replays a trace through a fresh `ButtHead` with `config`, starting at
+/// `start`, and calls `on_event` with every resulting event and the instant it
+/// fired at.
+///
+/// Between samples, `update()` is called exactly when the returned
+/// `ServiceTiming` asks for it, so timed events (`Click`, `Hold`, ...) fire
+/// where `config` would have fired them on-device. After the last sample, the
+/// replay continues for at most `tail`.
+pub fn replay<I, F>(
+    config: &'static Config<I::Duration>,
+    samples: impl IntoIterator<Item = TraceSample>,
+    start: I,
+    tail: I::Duration,
+    on_event: F,
+) where
+    I: TimeInstant,
+    F: FnMut(I, Event<I::Duration, I>),
+{
+    let mut offset = 0u64;
+    let changes = samples.into_iter().map(move |sample| {
+        offset += sample.delta_ms as u64;
+        (offset, sample.level)
+    });
+    driver::drive(
+        &mut ButtHead::new(config),
+        start,
+        changes,
+        None,
+        tail.as_millis(),
+        on_event,
+    );
+}
@@ -0,0 +1,374 @@
+//! Timing-parameter inference from recorded presses.
+//!
+//! Computes press-duration and inter-click-gap distributions and recommends
+//! `click_timeout` and `hold_delay` values that minimise misclassification —
+//! double-clicks split into two singles, separate clicks merged into one
+//! multi-click, clicks read as holds and vice versa — while keeping the
+//! latency each value adds as low as possible.
+//!
+//! Presses can come labelled (grouped into the gestures the user meant) or
+//! unlabelled, in which case the gestures are inferred from the gaps between
+//! the clusters in the data.
+
+use std::vec::Vec;
+
+use crate::capture::LevelChange;
+use crate::{Config, TimeDuration};
+
+/// Relative safety margin added on top of the smallest error-minimising value,
+/// as long as the margin does not introduce new errors.
+pub const MARGIN_PERCENT: u64 = 25;
+
+/// A single press, in milliseconds on any common time base.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Press {
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+impl Press {
+    /// Returns how long the button was held down.
+    pub fn duration_ms(&self) -> u64 {
+        self.end_ms.saturating_sub(self.start_ms)
+    }
+}
+
+/// What the user meant by a gesture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Label {
+    /// A click or multi-click; every press is short.
+    Click,
+    /// A hold, possibly preceded by clicks; the last press is long.
+    Hold,
+}
+
+/// The presses forming one intended gesture, in chronological order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Gesture {
+    pub presses: Vec<Press>,
+    pub label: Label,
+}
+
+/// Summary statistics of a set of durations, in milliseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Stats {
+    pub count: usize,
+    pub min: u64,
+    pub median: u64,
+    pub p90: u64,
+    pub max: u64,
+}
+
+impl Stats {
+    /// Computes statistics of `values`. All fields are 0 if it is empty.
+    pub fn of(values: &[u64]) -> Self {
+        let mut sorted = values.to_vec();
+        sorted.sort_unstable();
+        let percentile = |p: usize| {
+            sorted
+                .get((sorted.len().saturating_sub(1) * p) / 100)
+                .copied()
+                .unwrap_or(0)
+        };
+        Self {
+            count: sorted.len(),
+            min: sorted.first().copied().unwrap_or(0),
+            median: percentile(50),
+            p90: percentile(90),
+            max: sorted.last().copied().unwrap_or(0),
+        }
+    }
+}
+
+/// Distributions measured from a set of presses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Analysis {
+    /// How long each press lasted.
+    pub press_durations: Stats,
+    /// Time from each release to the next press.
+    pub gaps: Stats,
+}
+
+/// How a pair of timing parameters performs on labelled data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Evaluation {
+    pub click_timeout_ms: u64,
+    pub hold_delay_ms: u64,
+    /// Multi-click gestures that would be split into several `Click`s.
+    pub split_multi_clicks: usize,
+    /// Separate click gestures that would merge into one multi-click.
+    pub merged_clicks: usize,
+    /// Click gestures in which a press would fire `Hold`.
+    pub clicks_as_holds: usize,
+    /// Hold gestures whose final press would be released before `Hold`.
+    pub holds_as_clicks: usize,
+    /// Delay between the final release of a click gesture and its `Click`
+    /// event. Equal to `click_timeout_ms`.
+    pub click_latency_ms: u64,
+    /// Delay between the press and the first `Hold` event. Equal to
+    /// `hold_delay_ms`.
+    pub hold_latency_ms: u64,
+}
+
+impl Evaluation {
+    /// Total number of misclassified gestures.
+    pub fn errors(&self) -> usize {
+        self.split_multi_clicks + self.merged_clicks + self.clicks_as_holds + self.holds_as_clicks
+    }
+}
+
+/// Recommended parameters and the trade-offs considered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recommendation {
+    /// The recommended `click_timeout` and `hold_delay`, evaluated.
+    pub best: Evaluation,
+    /// Every `click_timeout` candidate with the recommended `hold_delay`,
+    /// ordered by increasing latency, to show what lower latency would cost.
+    pub click_timeout_options: Vec<Evaluation>,
+    /// Every `hold_delay` candidate with the recommended `click_timeout`,
+    /// ordered by increasing latency.
+    pub hold_delay_options: Vec<Evaluation>,
+}
+
+/// Extracts presses from a level stream (e.g. from [`crate::capture`]).
+/// `active_low` selects which level means pressed. A press still open at the
+/// end of the stream is dropped.
+pub fn presses_from_changes(changes: &[LevelChange], active_low: bool) -> Vec<Press> {
+    let mut presses = Vec::new();
+    let mut start = None;
+    for change in changes {
+        let pressed = change.level != active_low;
+        let at_ms = change.at_ns / 1_000_000;
+        match (pressed, start) {
+            (true, None) => start = Some(at_ms),
+            (false, Some(start_ms)) => {
+                presses.push(Press {
+                    start_ms,
+                    end_ms: at_ms,
+                });
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    presses
+}
+
+/// Measures press-duration and gap distributions.
+pub fn analyze(presses: &[Press]) -> Analysis {
+    let durations: Vec<u64> = presses.iter().map(Press::duration_ms).collect();
+    Analysis {
+        press_durations: Stats::of(&durations),
+        gaps: Stats::of(&gaps(presses)),
+    }
+}
+
+/// Groups unlabelled presses into gestures.
+///
+/// Gaps are split into "within a gesture" and "between gestures" at the
+/// largest jump (by ratio) in their sorted values, and press durations into
+/// "click" and "hold" the same way. Both splits need clearly bimodal data to
+/// be meaningful; with a single cluster, every gap is taken to separate
+/// gestures and every press to be a click.
+pub fn infer_gestures(presses: &[Press]) -> Vec<Gesture> {
+    let gap_split = cluster_split(&gaps(presses));
+    let durations: Vec<u64> = presses.iter().map(Press::duration_ms).collect();
+    let hold_split = cluster_split(&durations);
+
+    let mut gestures: Vec<Gesture> = Vec::new();
+    let mut previous_end: Option<u64> = None;
+    for &press in presses {
+        let continues = previous_end.is_some_and(|end| {
+            gap_split.is_some_and(|split| press.start_ms.saturating_sub(end) < split)
+        });
+        let after_hold = gestures.last().is_some_and(|g| g.label == Label::Hold);
+        let label = if hold_split.is_some_and(|split| press.duration_ms() >= split) {
+            Label::Hold
+        } else {
+            Label::Click
+        };
+        match gestures.last_mut() {
+            Some(gesture) if continues && !after_hold => {
+                gesture.presses.push(press);
+                gesture.label = label;
+            }
+            _ => gestures.push(Gesture {
+                presses: std::vec![press],
+                label,
+            }),
+        }
+        previous_end = Some(press.end_ms);
+    }
+    gestures
+}
+
+/// Evaluates a pair of timing parameters against labelled gestures, which
+/// must be in chronological order.
+pub fn evaluate(gestures: &[Gesture], click_timeout_ms: u64, hold_delay_ms: u64) -> Evaluation {
+    let mut evaluation = Evaluation {
+        click_timeout_ms,
+        hold_delay_ms,
+        split_multi_clicks: 0,
+        merged_clicks: 0,
+        clicks_as_holds: 0,
+        holds_as_clicks: 0,
+        click_latency_ms: click_timeout_ms,
+        hold_latency_ms: hold_delay_ms,
+    };
+
+    for (i, gesture) in gestures.iter().enumerate() {
+        let presses = &gesture.presses;
+        if gaps(presses).iter().any(|&gap| gap >= click_timeout_ms) {
+            evaluation.split_multi_clicks += 1;
+        }
+
+        let (clicks, last) = match gesture.label {
+            Label::Click => (&presses[..], None),
+            Label::Hold => match presses.split_last() {
+                Some((last, clicks)) => (clicks, Some(last)),
+                None => (&presses[..], None),
+            },
+        };
+        if clicks.iter().any(|p| p.duration_ms() >= hold_delay_ms) {
+            evaluation.clicks_as_holds += 1;
+        }
+        if last.is_some_and(|p| p.duration_ms() < hold_delay_ms) {
+            evaluation.holds_as_clicks += 1;
+        }
+
+        // Only a gesture ending in a click leaves the state machine waiting
+        // for another press.
+        if gesture.label == Label::Click
+            && let (Some(end), Some(next)) = (
+                presses.last(),
+                gestures.get(i + 1).and_then(|g| g.presses.first()),
+            )
+            && next.start_ms.saturating_sub(end.end_ms) < click_timeout_ms
+        {
+            evaluation.merged_clicks += 1;
+        }
+    }
+    evaluation
+}
+
+/// Recommends `click_timeout` and `hold_delay` for labelled gestures.
+///
+/// For each parameter, the smallest value with the fewest errors is chosen
+/// and then padded by [`MARGIN_PERCENT`] if that does not add errors.
+/// `click_timeout` is derived from the gaps within gestures, with the gaps
+/// between gestures only bounding it from above, and `hold_delay` needs at
+/// least one hold gesture. A parameter the data says nothing about (no
+/// multi-click, or no hold) keeps its value from `current`.
+pub fn recommend<D: TimeDuration>(gestures: &[Gesture], current: &Config<D>) -> Recommendation {
+    let mut intra_gaps = Vec::new();
+    let mut inter_gaps = Vec::new();
+    let mut durations = Vec::new();
+    for (i, gesture) in gestures.iter().enumerate() {
+        intra_gaps.extend(gaps(&gesture.presses));
+        durations.extend(gesture.presses.iter().map(Press::duration_ms));
+        if let (Some(end), Some(next)) = (
+            gesture.presses.last(),
+            gestures.get(i + 1).and_then(|g| g.presses.first()),
+        ) {
+            inter_gaps.push(next.start_ms.saturating_sub(end.end_ms));
+        }
+    }
+
+    let timeout_candidates = candidates(&intra_gaps, &inter_gaps);
+    let hold_candidates = candidates(&durations, &[]);
+    let has_holds = gestures.iter().any(|g| g.label == Label::Hold);
+
+    // Hold misclassification does not depend on click_timeout and vice versa,
+    // so each parameter can be optimised on its own.
+    let hold_delay = has_holds
+        .then(|| {
+            choose(&hold_candidates, |h| {
+                let e = evaluate(gestures, u64::MAX, h);
+                e.clicks_as_holds + e.holds_as_clicks
+            })
+        })
+        .flatten()
+        .unwrap_or(current.hold_delay.as_millis());
+    // Only the gaps within gestures yield candidates; a larger value could
+    // only merge gestures, which the error count rules out.
+    let click_timeout = choose(&candidates(&intra_gaps, &[]), |t| {
+        let e = evaluate(gestures, t, hold_delay);
+        e.split_multi_clicks + e.merged_clicks
+    })
+    .unwrap_or(current.click_timeout.as_millis());
+
+    Recommendation {
+        best: evaluate(gestures, click_timeout, hold_delay),
+        click_timeout_options: timeout_candidates
+            .iter()
+            .map(|&t| evaluate(gestures, t, hold_delay))
+            .collect(),
+        hold_delay_options: hold_candidates
+            .iter()
+            .map(|&h| evaluate(gestures, click_timeout, h))
+            .collect(),
+    }
+}
+
+/// Recommends `click_timeout` and `hold_delay` for unlabelled presses, using
+/// [`infer_gestures`] to label them first.
+pub fn recommend_unlabelled<D: TimeDuration>(
+    presses: &[Press],
+    current: &Config<D>,
+) -> Recommendation {
+    recommend(&infer_gestures(presses), current)
+}
+
+/// Gaps between consecutive presses.
+fn gaps(presses: &[Press]) -> Vec<u64> {
+    presses
+        .windows(2)
+        .map(|pair| pair[1].start_ms.saturating_sub(pair[0].end_ms))
+        .collect()
+}
+
+/// Candidate thresholds: just above every observed value, plus each value
+/// itself, so every possible classification boundary is represented.
+fn candidates(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let mut values: Vec<u64> = a
+        .iter()
+        .chain(b)
+        .flat_map(|&v| [v.max(1), v.saturating_add(1)])
+        .collect();
+    values.sort_unstable();
+    values.dedup();
+    values
+}
+
+/// Picks the smallest candidate with the fewest errors, then pads it by
+/// `MARGIN_PERCENT` if the padded value is no worse. Returns `None` if there
+/// are no candidates.
+fn choose(candidates: &[u64], errors: impl Fn(u64) -> usize) -> Option<u64> {
+    let (best, fewest) = candidates
+        .iter()
+        .map(|&c| (c, errors(c)))
+        .min_by_key(|&(c, e)| (e, c))?;
+    let padded = best.saturating_add(best.saturating_mul(MARGIN_PERCENT) / 100);
+    if errors(padded) <= fewest {
+        Some(padded)
+    } else {
+        Some(best)
+    }
+}
+
+/// Returns the threshold separating the two clusters of `values` at the
+/// largest ratio between neighbouring sorted values, or `None` if there is no
+/// clear split (fewer than two values, or no jump of at least 2x).
+fn cluster_split(values: &[u64]) -> Option<u64> {
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+    sorted
+        .windows(2)
+        .filter(|pair| pair[1] >= pair[0].max(1) * 2)
+        .max_by(|a, b| {
+            let ra = a[1] as f64 / a[0].max(1) as f64;
+            let rb = b[1] as f64 / b[0].max(1) as f64;
+            ra.total_cmp(&rb)
+        })
+        .map(|pair| pair[0] + (pair[1] - pair[0]) / 2)
+}
@@ -0,0 +1,188 @@
+use crate::{ButtHead, ButtonState, Config, ServiceTiming, TimeDuration, TimeInstant};
+
+/// An event produced by [`TwoHandControl`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TwoHandEvent {
+    /// Both buttons were pressed within the synchronous window. The output is
+    /// active until `Disengaged`.
+    Engaged,
+
+    /// A button was released while engaged. The output is inactive, and both
+    /// buttons must be released before the next cycle.
+    Disengaged,
+
+    /// The buttons were operated in a way that must not activate the output.
+    /// Both buttons must be released before the next cycle.
+    Fault(TwoHandFault),
+}
+
+/// Why a [`TwoHandControl`] refused to engage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TwoHandFault {
+    /// The second button was not pressed before the synchronous window ran
+    /// out.
+    SyncTimeout,
+
+    /// Both buttons were pressed again without both having been released
+    /// since the previous cycle (e.g. one button is tied down).
+    ReleaseRequired,
+}
+
+/// The result of a single [`TwoHandControl::update`] call.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TwoHandResult<D: TimeDuration> {
+    /// The event produced by this update, if any.
+    pub event: Option<TwoHandEvent>,
+    /// When to call `update()` again. See [`ServiceTiming`].
+    pub next_service: ServiceTiming<D>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    /// Both buttons were released; a new cycle may begin.
+    Ready,
+    Engaged,
+    /// Waiting for both buttons to be released.
+    Locked,
+}
+
+/// Two-hand safety control over two [`ButtHead`]s.
+///
+/// The output engages only when both buttons are pressed less than
+/// `sync_window` apart, and stays engaged only while both remain
+/// pressed. Releasing either disengages it, and a new cycle requires both
+/// buttons to have been released first (anti-tie-down). This also applies at
+/// start-up: buttons already held on the first update do not engage.
+///
+/// A button counts as pressed once it has emitted `Press`, so its
+/// `active_low` and `min_press_duration` settings apply: pulses shorter than
+/// the minimum never engage the output. The sync window compares the original
+/// press instants. With `confirm_duration` set, a press counts from
+/// `ConfirmStarted`: confirmation does not delay engagement. The buttons' own
+/// gesture events are not reported.
+pub struct TwoHandControl<I: TimeInstant> {
+    left: ButtHead<I>,
+    right: ButtHead<I>,
+    sync_window: I::Duration,
+    phase: Phase,
+    both_pressed: bool,
+}
+
+impl<I: TimeInstant> TwoHandControl<I> {
+    /// Creates a control whose buttons both use `config`. The second press
+    /// must follow the first in less than `sync_window`.
+    pub fn new(config: &'static Config<I::Duration>, sync_window: I::Duration) -> Self {
+        Self {
+            left: ButtHead::new(config),
+            right: ButtHead::new(config),
+            sync_window,
+            phase: Phase::Locked,
+            both_pressed: false,
+        }
+    }
+
+    /// Returns the left button.
+    pub fn left(&self) -> &ButtHead<I> {
+        &self.left
+    }
+
+    /// Returns the right button.
+    pub fn right(&self) -> &ButtHead<I> {
+        &self.right
+    }
+
+    /// Returns the configured synchronous window.
+    pub fn sync_window(&self) -> I::Duration {
+        self.sync_window
+    }
+
+    /// Returns `true` while the output is active.
+    pub fn is_engaged(&self) -> bool {
+        self.phase == Phase::Engaged
+    }
+
+    /// Feeds both raw pin levels at `now`. Call on every input change of
+    /// either button and whenever `next_service` asks for it, so that a
+    /// missed synchronous window is reported on time.
+    pub fn update(
+        &mut self,
+        left_pressed: bool,
+        right_pressed: bool,
+        now: I,
+    ) -> TwoHandResult<I::Duration> {
+        let left_timing = self.left.update(left_pressed, now).next_service;
+        let right_timing = self.right.update(right_pressed, now).next_service;
+
+        let left_at = Self::pressed_at(&self.left);
+        let right_at = Self::pressed_at(&self.right);
+        let was_both_pressed = self.both_pressed;
+        self.both_pressed = left_at.is_some() && right_at.is_some();
+
+        let event = match (self.phase, left_at, right_at) {
+            (Phase::Ready, Some(left_at), Some(right_at)) => {
+                let left_ms = now.duration_since(left_at).as_millis();
+                let right_ms = now.duration_since(right_at).as_millis();
+                if left_ms.abs_diff(right_ms) < self.sync_window.as_millis() {
+                    self.phase = Phase::Engaged;
+                    Some(TwoHandEvent::Engaged)
+                } else {
+                    self.fault(TwoHandFault::SyncTimeout)
+                }
+            }
+            (Phase::Ready, Some(at), None) | (Phase::Ready, None, Some(at))
+                if now.duration_since(at).as_millis() >= self.sync_window.as_millis() =>
+            {
+                self.fault(TwoHandFault::SyncTimeout)
+            }
+            (Phase::Engaged, Some(_), Some(_)) => None,
+            (Phase::Engaged, _, _) => {
+                self.phase = Phase::Locked;
+                Some(TwoHandEvent::Disengaged)
+            }
+            (Phase::Locked, Some(_), Some(_)) if !was_both_pressed => {
+                self.fault(TwoHandFault::ReleaseRequired)
+            }
+            _ => None,
+        };
+
+        if self.phase == Phase::Locked && left_at.is_none() && right_at.is_none() {
+            self.phase = Phase::Ready;
+        }
+
+        // The buttons' own timings cover deferred presses (`min_press_duration`)
+        // and queued events.
+        let window = match (self.phase, left_at, right_at) {
+            (Phase::Ready, Some(at), None) | (Phase::Ready, None, Some(at)) => {
+                ServiceTiming::Delay(self.sync_window.saturating_sub(now.duration_since(at)))
+            }
+            _ => ServiceTiming::Idle,
+        };
+        let next_service = window.min(left_timing).min(right_timing);
+
+        TwoHandResult {
+            event,
+            next_service,
+        }
+    }
+
+    /// Returns the press instant if the button has emitted `Press` (or
+    /// `ConfirmStarted`) for its current press.
+    fn pressed_at(button: &ButtHead<I>) -> Option<I> {
+        match button.state() {
+            ButtonState::Pressed { since, .. }
+            | ButtonState::Stuck { since }
+            | ButtonState::Suppressed { since }
+            | ButtonState::Confirming { since }
+            | ButtonState::Confirmed { since } => Some(since),
+            _ => None,
+        }
+    }
+
+    fn fault(&mut self, fault: TwoHandFault) -> Option<TwoHandEvent> {
+        self.phase = Phase::Locked;
+        Some(TwoHandEvent::Fault(fault))
+    }
+}
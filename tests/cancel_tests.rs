@@ -0,0 +1,187 @@
+mod common;
+
+use butt_head::{ButtHead, ButtonState, Config, Event, ServiceTiming};
+use common::{CONFIG, TestDuration, TestInstant, new_button};
+
+static STUCK_CONFIG: Config<TestDuration> = Config {
+    max_press_duration: Some(TestDuration(1000)),
+    ..CONFIG
+};
+
+// --- Pressed ---
+
+#[test]
+fn cancel_during_press_suppresses_hold() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+
+    assert!(button.cancel_gesture());
+
+    let result = button.update(true, TestInstant::ms(500));
+    assert_eq!(result.event, None);
+    assert_eq!(result.next_service, ServiceTiming::Idle);
+}
+
+#[test]
+fn release_after_cancelled_press_emits_nothing() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.cancel_gesture();
+
+    let result = button.update(false, TestInstant::ms(100));
+    assert_eq!(result.event, None);
+    assert_eq!(result.next_service, ServiceTiming::Idle);
+
+    // No Click after the timeout either.
+    assert_eq!(button.update(false, TestInstant::ms(400)).event, None);
+}
+
+#[test]
+fn cancel_during_hold_suppresses_release_and_hold_end() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.update(true, TestInstant::ms(500)); // Hold level 0
+    button.cancel_gesture();
+
+    assert_eq!(button.update(true, TestInstant::ms(700)).event, None);
+    assert_eq!(button.update(false, TestInstant::ms(800)).event, None);
+    assert_eq!(button.update(false, TestInstant::ms(801)).event, None);
+}
+
+#[test]
+fn cancel_drops_clicks_preceding_the_press() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.update(false, TestInstant::ms(100));
+    button.update(true, TestInstant::ms(200));
+    button.cancel_gesture();
+    button.update(false, TestInstant::ms(300));
+
+    assert_eq!(button.update(false, TestInstant::ms(1000)).event, None);
+}
+
+#[test]
+fn cancelled_press_suppresses_stuck() {
+    let mut button = ButtHead::new(&STUCK_CONFIG);
+    button.update(true, TestInstant::ms(0));
+    button.cancel_gesture();
+
+    assert_eq!(button.update(true, TestInstant::ms(1000)).event, None);
+    assert!(!button.is_stuck());
+}
+
+#[test]
+fn next_press_after_cancel_starts_fresh_gesture() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.update(false, TestInstant::ms(100));
+    button.update(true, TestInstant::ms(200));
+    button.cancel_gesture();
+    button.update(false, TestInstant::ms(300));
+
+    let result = button.update(true, TestInstant::ms(350));
+    assert_eq!(
+        result.event,
+        Some(Event::Press {
+            at: TestInstant::ms(350)
+        })
+    );
+    let result = button.update(true, TestInstant::ms(850));
+    assert_eq!(
+        result.event,
+        Some(Event::Hold {
+            clicks_before: 0,
+            level: 0
+        })
+    );
+}
+
+#[test]
+fn suppressed_press_reports_state() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(100));
+    button.suppress_until_release();
+
+    assert_eq!(
+        button.state(),
+        ButtonState::Suppressed {
+            since: TestInstant::ms(100)
+        }
+    );
+    assert!(button.is_pressed());
+    assert_eq!(button.press_instant(), Some(TestInstant::ms(100)));
+    assert_eq!(
+        button.pressed_duration(TestInstant::ms(300)),
+        Some(TestDuration(200))
+    );
+    assert_eq!(button.time_until_next_event(TestInstant::ms(300)), None);
+    assert_eq!(button.pending_click_count(), 0);
+}
+
+// --- WaitForMultiClick ---
+
+#[test]
+fn cancel_while_waiting_drops_pending_click() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.update(false, TestInstant::ms(100));
+
+    assert!(button.cancel_gesture());
+
+    assert_eq!(button.state(), ButtonState::Idle);
+    assert_eq!(button.update(false, TestInstant::ms(400)).event, None);
+}
+
+#[test]
+fn suppress_until_release_does_nothing_while_waiting() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.update(false, TestInstant::ms(100));
+
+    assert!(!button.suppress_until_release());
+
+    let result = button.update(false, TestInstant::ms(400));
+    assert_eq!(result.event, Some(Event::Click { count: 1 }));
+}
+
+// --- Nothing to cancel ---
+
+#[test]
+fn cancel_when_idle_returns_false() {
+    let mut button = new_button();
+
+    assert!(!button.cancel_gesture());
+    assert!(!button.suppress_until_release());
+}
+
+#[test]
+fn cancel_when_stuck_returns_false() {
+    let mut button = ButtHead::new(&STUCK_CONFIG);
+    button.update(true, TestInstant::ms(0));
+    button.update(true, TestInstant::ms(1000)); // Stuck
+
+    assert!(!button.cancel_gesture());
+    assert!(button.is_stuck());
+}
+
+#[test]
+fn cancel_twice_returns_false_the_second_time() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+
+    assert!(button.cancel_gesture());
+    assert!(!button.cancel_gesture());
+}
+
+#[test]
+fn queued_hold_end_is_still_delivered() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.update(true, TestInstant::ms(500)); // Hold
+    button.update(false, TestInstant::ms(600)); // Release, HoldEnd queued
+
+    assert!(!button.cancel_gesture());
+
+    let result = button.update(false, TestInstant::ms(601));
+    assert!(matches!(result.event, Some(Event::HoldEnd { .. })));
+}
@@ -0,0 +1,199 @@
+#![cfg(feature = "std")]
+
+use butt_head::capture::{self, LevelChange, ParseError};
+use butt_head::sim::{SimDuration, SimInstant};
+use butt_head::{Config, Event};
+
+static CONFIG: Config<SimDuration> = Config {
+    active_low: true,
+    ..Config::new(SimDuration(300), SimDuration(500), SimDuration(200))
+};
+
+const VCD: &str = "\
+$date Mon Jan 1 00:00:00 2026 $end
+$version sigrok $end
+$comment
+  Acquisition with 2/8 channels at 1 MHz
+$end
+$timescale 1 us $end
+$scope module sigrok $end
+$var wire 1 ! D0 $end
+$var wire 1 \" D1 $end
+$upscope $end
+$enddefinitions $end
+#0 1! 1\"
+#100000 0!
+#180000 1!
+#250000 0! 0\"
+#300000 1!
+#400000 x\"
+";
+
+const CSV: &str = "\
+; CSV, generated by libsigrok
+; Channels (2/8)
+; Samplerate: 1 kHz
+D0,D1
+1,1
+1,1
+0,1
+0,0
+1,0
+";
+
+const CSV_WITH_TIME: &str = "\
+Time [s],D0
+0.000,1
+0.100,0
+0.180,1
+";
+
+fn change(at_ms: u64, level: bool) -> LevelChange {
+    LevelChange {
+        at_ns: at_ms * 1_000_000,
+        level,
+    }
+}
+
+// --- VCD ---
+
+#[test]
+fn vcd_parses_signal_changes_with_timescale() {
+    let changes = capture::parse_vcd(VCD, "D0").unwrap();
+
+    assert_eq!(
+        changes,
+        [
+            change(0, true),
+            change(100, false),
+            change(180, true),
+            change(250, false),
+            change(300, true),
+        ]
+    );
+}
+
+#[test]
+fn vcd_ignores_unknown_values() {
+    let changes = capture::parse_vcd(VCD, "D1").unwrap();
+
+    assert_eq!(changes, [change(0, true), change(250, false)]);
+}
+
+#[test]
+fn vcd_missing_signal_is_an_error() {
+    assert_eq!(
+        capture::parse_vcd(VCD, "D7"),
+        Err(ParseError::SignalNotFound("D7".into()))
+    );
+}
+
+#[test]
+fn vcd_bad_timescale_is_an_error() {
+    let vcd = "$timescale 1 parsec $end\n$var wire 1 ! D0 $end\n";
+    assert_eq!(
+        capture::parse_vcd(vcd, "D0"),
+        Err(ParseError::InvalidTimescale)
+    );
+}
+
+#[test]
+fn vcd_non_ascii_value_is_an_error() {
+    let vcd = "$var wire 1 ! D0 $end\n#0 1!\n#10 é!\n";
+    assert_eq!(
+        capture::parse_vcd(vcd, "D0"),
+        Err(ParseError::InvalidLine(3))
+    );
+}
+
+// --- sigrok CSV ---
+
+#[test]
+fn csv_uses_samplerate_comment() {
+    let changes = capture::parse_sigrok_csv(CSV, "D0", None).unwrap();
+
+    assert_eq!(
+        changes,
+        [change(0, true), change(2, false), change(4, true)]
+    );
+}
+
+#[test]
+fn csv_explicit_samplerate_overrides_comment() {
+    let changes = capture::parse_sigrok_csv(CSV, "D1", Some(100)).unwrap();
+
+    assert_eq!(changes, [change(0, true), change(30, false)]);
+}
+
+#[test]
+fn csv_uses_time_column() {
+    let changes = capture::parse_sigrok_csv(CSV_WITH_TIME, "D0", None).unwrap();
+
+    assert_eq!(
+        changes,
+        [change(0, true), change(100, false), change(180, true)]
+    );
+}
+
+#[test]
+fn csv_time_column_uses_header_unit() {
+    let changes = capture::parse_sigrok_csv("Time [ms],D0\n0,1\n5,0\n", "D0", None).unwrap();
+
+    assert_eq!(changes, [change(0, true), change(5, false)]);
+    assert_eq!(changes[1].at_ns, 5_000_000);
+}
+
+#[test]
+fn csv_time_column_with_unknown_unit_is_an_error() {
+    assert_eq!(
+        capture::parse_sigrok_csv("; comment\nTime [min],D0\n0,1\n", "D0", None),
+        Err(ParseError::InvalidLine(2))
+    );
+}
+
+#[test]
+fn csv_without_time_or_samplerate_is_an_error() {
+    assert_eq!(
+        capture::parse_sigrok_csv("D0\n1\n0\n", "D0", None),
+        Err(ParseError::MissingSampleRate)
+    );
+}
+
+#[test]
+fn csv_bad_value_reports_line() {
+    assert_eq!(
+        capture::parse_sigrok_csv("D0\n1\nfoo\n", "D0", Some(1000)),
+        Err(ParseError::InvalidLine(3))
+    );
+}
+
+// --- run ---
+
+#[test]
+fn run_reports_events_for_capture() {
+    // Active-low button: two presses, 80ms and 50ms, 70ms apart.
+    let changes = capture::parse_vcd(VCD, "D0").unwrap();
+
+    let events = capture::run(&CONFIG, &changes);
+
+    assert_eq!(
+        events.last(),
+        Some(&(SimInstant(600), Event::Click { count: 2 }))
+    );
+}
+
+#[test]
+fn run_shows_effect_of_click_timeout() {
+    static SHORT_TIMEOUT: Config<SimDuration> = Config {
+        click_timeout: SimDuration(50),
+        ..CONFIG
+    };
+    let changes = capture::parse_vcd(VCD, "D0").unwrap();
+
+    let clicks: Vec<_> = capture::run(&SHORT_TIMEOUT, &changes)
+        .into_iter()
+        .filter(|(_, e)| matches!(e, Event::Click { .. }))
+        .collect();
+
+    assert_eq!(clicks.len(), 2);
+}
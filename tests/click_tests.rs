@@ -48,27 +48,18 @@ fn cancel_pending_click_allows_fresh_click_after_cancellation() {
 // --- max_click_count configs ---
 
 static MAX_1_CONFIG: Config<TestDuration> = Config {
-    active_low: false,
-    click_timeout: TestDuration(300),
-    hold_delay: TestDuration(500),
-    hold_interval: TestDuration(200),
     max_click_count: Some(1),
+    ..CONFIG
 };
 
 static MAX_2_CONFIG: Config<TestDuration> = Config {
-    active_low: false,
-    click_timeout: TestDuration(300),
-    hold_delay: TestDuration(500),
-    hold_interval: TestDuration(200),
     max_click_count: Some(2),
+    ..CONFIG
 };
 
 static ACTIVE_LOW_CONFIG: Config<TestDuration> = Config {
     active_low: true,
-    click_timeout: TestDuration(300),
-    hold_delay: TestDuration(500),
-    hold_interval: TestDuration(200),
-    max_click_count: None,
+    ..CONFIG
 };
 
 // --- Single click ---
@@ -189,6 +180,8 @@ fn long_press_does_not_emit_click() {
     button.update(true, TestInstant::ms(500));
     // Release after hold
     button.update(false, TestInstant::ms(600));
+    // Immediate follow-up reports the end of the hold
+    button.update(false, TestInstant::ms(600));
     // Advance well past click_timeout
     let result = button.update(false, TestInstant::ms(1000));
 
@@ -1,8 +1,12 @@
+// Shared by every integration test binary; not all of them use every helper.
+#![allow(dead_code)]
+
 use butt_head::{ButtHead, Config, TimeDuration, TimeInstant};
 
 // --- Time types ---
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TestDuration(pub u64);
 
 impl TimeDuration for TestDuration {
@@ -22,6 +26,7 @@ impl TimeDuration for TestDuration {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TestInstant(pub u64);
 
 impl TestInstant {
@@ -44,17 +49,16 @@ impl TimeInstant for TestInstant {
     fn checked_sub(self, duration: TestDuration) -> Option<Self> {
         self.0.checked_sub(duration.0).map(TestInstant)
     }
+
+    fn checked_duration_since(&self, earlier: Self) -> Option<TestDuration> {
+        self.0.checked_sub(earlier.0).map(TestDuration)
+    }
 }
 
 // --- Configs ---
 
-pub static CONFIG: Config<TestDuration> = Config {
-    active_low: false,
-    click_timeout: TestDuration(300),
-    hold_delay: TestDuration(500),
-    hold_interval: TestDuration(200),
-    max_click_count: None,
-};
+pub static CONFIG: Config<TestDuration> =
+    Config::new(TestDuration(300), TestDuration(500), TestDuration(200));
 
 // --- Helpers ---
 
@@ -0,0 +1,350 @@
+mod common;
+
+use butt_head::{ButtHead, ButtonState, Config, Event, HoldProgress, ServiceTiming};
+use common::{CONFIG, TestDuration, TestInstant};
+
+static CONFIRM: Config<TestDuration> = Config {
+    confirm_duration: Some(TestDuration(3000)),
+    ..CONFIG
+};
+
+static PROGRESS: Config<TestDuration> = Config {
+    confirm_progress_interval: Some(TestDuration(1000)),
+    ..CONFIRM
+};
+
+static COOLDOWN: Config<TestDuration> = Config {
+    confirm_cooldown: Some(TestDuration(2000)),
+    ..CONFIRM
+};
+
+type TestEvent = Event<TestDuration, TestInstant>;
+
+fn progress(elapsed: u64) -> Option<TestEvent> {
+    Some(Event::ConfirmProgress {
+        elapsed: TestDuration(elapsed),
+    })
+}
+
+/// Presses at 0 and holds until `Confirmed` at 3000.
+fn confirmed(config: &'static Config<TestDuration>) -> ButtHead<TestInstant> {
+    let mut button = ButtHead::new(config);
+    button.update(true, TestInstant::ms(0));
+    assert_eq!(
+        button.update(true, TestInstant::ms(3000)).event,
+        Some(Event::Confirmed)
+    );
+    button
+}
+
+// --- Confirm ---
+
+#[test]
+fn press_emits_confirm_started() {
+    let mut button = ButtHead::new(&CONFIRM);
+
+    let result = button.update(true, TestInstant::ms(100));
+
+    assert_eq!(
+        result.event,
+        Some(Event::ConfirmStarted {
+            at: TestInstant::ms(100)
+        })
+    );
+    assert_eq!(
+        result.next_service,
+        ServiceTiming::Delay(TestDuration(3000))
+    );
+    assert_eq!(
+        button.state(),
+        ButtonState::Confirming {
+            since: TestInstant::ms(100)
+        }
+    );
+}
+
+#[test]
+fn holding_for_confirm_duration_confirms_once() {
+    let mut button = ButtHead::new(&CONFIRM);
+    button.update(true, TestInstant::ms(0));
+
+    assert_eq!(button.update(true, TestInstant::ms(2999)).event, None);
+    let result = button.update(true, TestInstant::ms(3000));
+    assert_eq!(result.event, Some(Event::Confirmed));
+    assert_eq!(result.next_service, ServiceTiming::Idle);
+    assert_eq!(button.update(true, TestInstant::ms(10_000)).event, None);
+}
+
+#[test]
+fn no_hold_events_fire_in_confirm_mode() {
+    let mut button = ButtHead::new(&CONFIRM);
+    button.update(true, TestInstant::ms(0));
+
+    for t in (100..3000).step_by(100) {
+        assert_eq!(button.update(true, TestInstant::ms(t)).event, None);
+    }
+}
+
+#[test]
+fn release_after_confirmed_emits_nothing() {
+    let mut button = confirmed(&CONFIRM);
+
+    let result = button.update(false, TestInstant::ms(3500));
+
+    assert_eq!(result.event, None);
+    assert_eq!(result.next_service, ServiceTiming::Idle);
+    assert_eq!(button.state(), ButtonState::Idle);
+}
+
+#[test]
+fn late_release_after_confirm_duration_still_confirms() {
+    let mut button = ButtHead::new(&CONFIRM);
+    button.update(true, TestInstant::ms(0));
+
+    // Not serviced at 3000; the release arrives first.
+    let result = button.update(false, TestInstant::ms(3200));
+
+    assert_eq!(result.event, Some(Event::Confirmed));
+    assert_eq!(button.state(), ButtonState::Idle);
+}
+
+// --- Abort ---
+
+#[test]
+fn early_release_aborts_without_click() {
+    let mut button = ButtHead::new(&CONFIRM);
+    button.update(true, TestInstant::ms(0));
+
+    let result = button.update(false, TestInstant::ms(1200));
+    assert_eq!(
+        result.event,
+        Some(Event::ConfirmAborted {
+            held_for: TestDuration(1200)
+        })
+    );
+    assert_eq!(result.next_service, ServiceTiming::Idle);
+
+    assert_eq!(button.update(false, TestInstant::ms(5000)).event, None);
+}
+
+#[test]
+fn short_tap_aborts() {
+    let mut button = ButtHead::new(&CONFIRM);
+    button.update(true, TestInstant::ms(0));
+
+    assert_eq!(
+        button.update(false, TestInstant::ms(50)).event,
+        Some(Event::ConfirmAborted {
+            held_for: TestDuration(50)
+        })
+    );
+}
+
+#[test]
+fn cancelled_confirm_emits_nothing_on_release() {
+    let mut button = ButtHead::new(&CONFIRM);
+    button.update(true, TestInstant::ms(0));
+
+    assert!(button.cancel_gesture());
+    assert_eq!(button.update(true, TestInstant::ms(3000)).event, None);
+    assert_eq!(button.update(false, TestInstant::ms(3100)).event, None);
+}
+
+// --- Progress ---
+
+#[test]
+fn progress_fires_every_interval_before_confirm() {
+    let mut button = ButtHead::new(&PROGRESS);
+
+    let result = button.update(true, TestInstant::ms(0));
+    assert_eq!(
+        result.next_service,
+        ServiceTiming::Delay(TestDuration(1000))
+    );
+
+    let result = button.update(true, TestInstant::ms(1000));
+    assert_eq!(result.event, progress(1000));
+    assert_eq!(
+        result.next_service,
+        ServiceTiming::Delay(TestDuration(1000))
+    );
+
+    assert_eq!(
+        button.update(true, TestInstant::ms(2000)).event,
+        progress(2000)
+    );
+    // The tick that coincides with the confirm duration is `Confirmed`.
+    assert_eq!(
+        button.update(true, TestInstant::ms(3000)).event,
+        Some(Event::Confirmed)
+    );
+}
+
+#[test]
+fn late_progress_keeps_its_schedule() {
+    let mut button = ButtHead::new(&PROGRESS);
+    button.update(true, TestInstant::ms(0));
+
+    let result = button.update(true, TestInstant::ms(1300));
+
+    assert_eq!(result.event, progress(1300));
+    assert_eq!(result.next_service, ServiceTiming::Delay(TestDuration(700)));
+}
+
+#[test]
+fn hold_progress_tracks_confirm_duration() {
+    let mut button = ButtHead::new(&CONFIRM);
+    button.update(true, TestInstant::ms(0));
+
+    assert_eq!(
+        button.hold_progress(TestInstant::ms(1500)),
+        Some(HoldProgress {
+            elapsed: TestDuration(1500),
+            total: TestDuration(3000),
+        })
+    );
+    assert_eq!(
+        button.time_to_next_hold(TestInstant::ms(1500)),
+        Some(TestDuration(1500))
+    );
+}
+
+// --- Cool-down ---
+
+#[test]
+fn release_after_confirmed_starts_cooldown() {
+    let mut button = confirmed(&COOLDOWN);
+
+    let result = button.update(false, TestInstant::ms(3500));
+
+    assert_eq!(result.event, None);
+    assert_eq!(
+        result.next_service,
+        ServiceTiming::Delay(TestDuration(2000))
+    );
+    assert_eq!(
+        button.state(),
+        ButtonState::Cooldown {
+            since: TestInstant::ms(3500)
+        }
+    );
+}
+
+#[test]
+fn presses_during_cooldown_are_ignored() {
+    let mut button = confirmed(&COOLDOWN);
+    button.update(false, TestInstant::ms(3500));
+
+    assert_eq!(button.update(true, TestInstant::ms(4000)).event, None);
+    assert_eq!(button.update(false, TestInstant::ms(4100)).event, None);
+    assert_eq!(button.update(true, TestInstant::ms(4200)).event, None);
+    assert_eq!(button.update(false, TestInstant::ms(4300)).event, None);
+}
+
+#[test]
+fn cooldown_ends_after_its_duration() {
+    let mut button = confirmed(&COOLDOWN);
+    button.update(false, TestInstant::ms(3500));
+
+    let result = button.update(false, TestInstant::ms(5500));
+    assert_eq!(result.next_service, ServiceTiming::Idle);
+    assert_eq!(button.state(), ButtonState::Idle);
+
+    assert_eq!(
+        button.update(true, TestInstant::ms(6000)).event,
+        Some(Event::ConfirmStarted {
+            at: TestInstant::ms(6000)
+        })
+    );
+}
+
+#[test]
+fn press_after_unserviced_cooldown_starts_confirm() {
+    let mut button = confirmed(&COOLDOWN);
+    button.update(false, TestInstant::ms(3500));
+
+    assert_eq!(
+        button.update(true, TestInstant::ms(8000)).event,
+        Some(Event::ConfirmStarted {
+            at: TestInstant::ms(8000)
+        })
+    );
+}
+
+#[test]
+fn press_held_past_cooldown_must_be_released_first() {
+    let mut button = confirmed(&COOLDOWN);
+    button.update(false, TestInstant::ms(3500));
+    button.update(true, TestInstant::ms(5000));
+
+    button.update(true, TestInstant::ms(5500));
+    assert_eq!(
+        button.state(),
+        ButtonState::Suppressed {
+            since: TestInstant::ms(5000)
+        }
+    );
+    assert_eq!(button.update(true, TestInstant::ms(9000)).event, None);
+    assert_eq!(button.update(false, TestInstant::ms(9100)).event, None);
+
+    assert!(matches!(
+        button.update(true, TestInstant::ms(9200)).event,
+        Some(Event::ConfirmStarted { .. })
+    ));
+}
+
+#[test]
+fn aborted_confirm_has_no_cooldown() {
+    let mut button = ButtHead::new(&COOLDOWN);
+    button.update(true, TestInstant::ms(0));
+    button.update(false, TestInstant::ms(500));
+
+    assert!(matches!(
+        button.update(true, TestInstant::ms(600)).event,
+        Some(Event::ConfirmStarted { .. })
+    ));
+}
+
+// --- Interaction ---
+
+#[test]
+fn min_press_duration_defers_confirm_started() {
+    static MIN_PRESS: Config<TestDuration> = Config {
+        min_press_duration: Some(TestDuration(20)),
+        report_glitches: true,
+        ..CONFIRM
+    };
+    let mut button = ButtHead::new(&MIN_PRESS);
+
+    assert_eq!(button.update(true, TestInstant::ms(0)).event, None);
+    assert_eq!(
+        button.update(true, TestInstant::ms(20)).event,
+        Some(Event::ConfirmStarted {
+            at: TestInstant::ms(0)
+        })
+    );
+    assert_eq!(
+        button.update(true, TestInstant::ms(3000)).event,
+        Some(Event::Confirmed)
+    );
+}
+
+#[test]
+fn huge_progress_interval_does_not_overflow() {
+    static HUGE: Config<TestDuration> = Config {
+        confirm_duration: Some(TestDuration(u64::MAX)),
+        confirm_progress_interval: Some(TestDuration(u64::MAX / 2 + 1)),
+        ..CONFIG
+    };
+    let mut button = ButtHead::new(&HUGE);
+    button.update(true, TestInstant::ms(0));
+
+    let at = u64::MAX / 2 + 1;
+    assert_eq!(button.update(true, TestInstant::ms(at)).event, progress(at));
+    assert_eq!(
+        button.state(),
+        ButtonState::Confirming {
+            since: TestInstant::ms(0)
+        }
+    );
+}
@@ -0,0 +1,272 @@
+mod common;
+
+use butt_head::{
+    ButtonHandler, ButtonState, Config, DualChannelButton, Event, HandlerContext, ServiceTiming,
+};
+use common::{CONFIG, TestDuration, TestInstant};
+
+const DISCREPANCY: TestDuration = TestDuration(50);
+
+// Raw (NO, NC) levels with active-high wiring.
+const RELEASED: (bool, bool) = (false, true);
+const PRESSED: (bool, bool) = (true, false);
+const BOTH_OPEN: (bool, bool) = (false, false);
+const BOTH_CLOSED: (bool, bool) = (true, true);
+
+fn new_button() -> DualChannelButton<TestInstant> {
+    DualChannelButton::new(&CONFIG, DISCREPANCY)
+}
+
+fn update(
+    button: &mut DualChannelButton<TestInstant>,
+    (no, nc): (bool, bool),
+    ms: u64,
+) -> butt_head::UpdateResult<TestDuration, TestInstant> {
+    button.update(no, nc, TestInstant::ms(ms))
+}
+
+// --- Consistent channels ---
+
+#[test]
+fn complementary_levels_drive_the_button() {
+    let mut button = new_button();
+
+    assert_eq!(
+        update(&mut button, PRESSED, 0).event,
+        Some(Event::Press {
+            at: TestInstant::ms(0)
+        })
+    );
+    assert!(matches!(
+        update(&mut button, RELEASED, 100).event,
+        Some(Event::Release { .. })
+    ));
+    assert_eq!(
+        update(&mut button, RELEASED, 400).event,
+        Some(Event::Click { count: 1 })
+    );
+}
+
+#[test]
+fn consistent_channels_keep_button_timing() {
+    let mut button = new_button();
+
+    let result = update(&mut button, PRESSED, 0);
+
+    assert_eq!(result.next_service, ServiceTiming::Delay(TestDuration(500)));
+}
+
+// --- Tolerated discrepancy ---
+
+#[test]
+fn brief_discrepancy_during_press_is_tolerated() {
+    let mut button = new_button();
+
+    // NO closes first, NC opens 10ms later.
+    assert_eq!(update(&mut button, BOTH_CLOSED, 0).event, None);
+    assert!(!button.button().is_pressed());
+    assert!(matches!(
+        update(&mut button, PRESSED, 10).event,
+        Some(Event::Press { .. })
+    ));
+    assert!(!button.is_faulted());
+}
+
+#[test]
+fn brief_discrepancy_holds_last_consistent_level() {
+    let mut button = new_button();
+    update(&mut button, PRESSED, 0);
+
+    // NC closes before NO opens on release.
+    assert_eq!(update(&mut button, BOTH_CLOSED, 100).event, None);
+    assert!(button.button().is_pressed());
+    assert!(matches!(
+        update(&mut button, RELEASED, 120).event,
+        Some(Event::Release { .. })
+    ));
+}
+
+#[test]
+fn discrepancy_schedules_fault_deadline() {
+    let mut button = new_button();
+
+    let result = update(&mut button, BOTH_OPEN, 0);
+    assert_eq!(result.next_service, ServiceTiming::Delay(DISCREPANCY));
+
+    let result = update(&mut button, BOTH_OPEN, 30);
+    assert_eq!(result.next_service, ServiceTiming::Delay(TestDuration(20)));
+}
+
+#[test]
+fn discrepancy_deadline_does_not_delay_button_events() {
+    let mut button = new_button();
+    update(&mut button, PRESSED, 0);
+    update(&mut button, RELEASED, 100);
+
+    // Waiting for the Click at 400, but the fault deadline is sooner.
+    let result = update(&mut button, BOTH_CLOSED, 380);
+    assert_eq!(result.next_service, ServiceTiming::Delay(TestDuration(20)));
+
+    let result = update(&mut button, RELEASED, 390);
+    assert_eq!(result.next_service, ServiceTiming::Delay(TestDuration(10)));
+}
+
+#[test]
+fn tolerance_restarts_after_agreement() {
+    let mut button = new_button();
+    update(&mut button, BOTH_OPEN, 0);
+    update(&mut button, RELEASED, 40);
+
+    assert_eq!(update(&mut button, BOTH_OPEN, 60).event, None);
+    assert_eq!(update(&mut button, BOTH_OPEN, 100).event, None);
+}
+
+// --- Fault ---
+
+#[test]
+fn lasting_discrepancy_raises_channel_fault() {
+    let mut button = new_button();
+    update(&mut button, BOTH_CLOSED, 0);
+
+    let result = update(&mut button, BOTH_CLOSED, 50);
+
+    assert_eq!(
+        result.event,
+        Some(Event::ChannelFault { no: true, nc: true })
+    );
+    assert_eq!(result.next_service, ServiceTiming::Idle);
+    assert!(button.is_faulted());
+}
+
+#[test]
+fn channel_fault_fires_once() {
+    let mut button = new_button();
+    update(&mut button, BOTH_OPEN, 0);
+    update(&mut button, BOTH_OPEN, 50);
+
+    assert_eq!(update(&mut button, BOTH_OPEN, 1000).event, None);
+    assert_eq!(update(&mut button, BOTH_CLOSED, 2000).event, None);
+}
+
+#[test]
+fn fault_during_press_emits_no_release() {
+    let mut button = new_button();
+    update(&mut button, PRESSED, 0);
+    update(&mut button, BOTH_OPEN, 100);
+    update(&mut button, BOTH_OPEN, 150);
+
+    assert_eq!(button.button().state(), ButtonState::Idle);
+    assert!(!button.button().is_enabled());
+    assert_eq!(update(&mut button, BOTH_OPEN, 1000).event, None);
+}
+
+#[test]
+fn late_service_reports_fault_immediately() {
+    let mut button = new_button();
+    update(&mut button, PRESSED, 0);
+    update(&mut button, BOTH_OPEN, 100);
+
+    assert!(matches!(
+        update(&mut button, BOTH_OPEN, 400).event,
+        Some(Event::ChannelFault { .. })
+    ));
+}
+
+// --- Recovery ---
+
+#[test]
+fn agreement_on_release_recovers_silently() {
+    let mut button = new_button();
+    update(&mut button, BOTH_OPEN, 0);
+    update(&mut button, BOTH_OPEN, 50);
+
+    assert_eq!(update(&mut button, RELEASED, 200).event, None);
+    assert!(!button.is_faulted());
+    assert!(button.button().is_enabled());
+
+    assert!(matches!(
+        update(&mut button, PRESSED, 300).event,
+        Some(Event::Press { .. })
+    ));
+}
+
+#[test]
+fn agreement_while_pressed_adopts_press_by_default() {
+    let mut button = new_button();
+    update(&mut button, BOTH_CLOSED, 0);
+    update(&mut button, BOTH_CLOSED, 50);
+
+    assert_eq!(
+        update(&mut button, PRESSED, 200).event,
+        Some(Event::Press {
+            at: TestInstant::ms(200)
+        })
+    );
+}
+
+#[test]
+fn agreement_while_pressed_can_require_release() {
+    static REQUIRE_RELEASE: Config<TestDuration> = Config {
+        require_release_on_enable: true,
+        ..CONFIG
+    };
+    let mut button = DualChannelButton::new(&REQUIRE_RELEASE, DISCREPANCY);
+    update(&mut button, BOTH_CLOSED, 0);
+    update(&mut button, BOTH_CLOSED, 50);
+
+    assert_eq!(update(&mut button, PRESSED, 200).event, None);
+    assert_eq!(update(&mut button, RELEASED, 300).event, None);
+    assert!(matches!(
+        update(&mut button, PRESSED, 400).event,
+        Some(Event::Press { .. })
+    ));
+}
+
+// --- Wiring ---
+
+#[test]
+fn active_low_applies_to_normally_open_channel() {
+    static ACTIVE_LOW: Config<TestDuration> = Config {
+        active_low: true,
+        ..CONFIG
+    };
+    let mut button = DualChannelButton::new(&ACTIVE_LOW, DISCREPANCY);
+
+    // Released: NO high, NC low.
+    assert_eq!(button.update(true, false, TestInstant::ms(0)).event, None);
+    // A discrepancy straight away keeps the released level.
+    assert_eq!(button.update(false, false, TestInstant::ms(10)).event, None);
+    assert!(matches!(
+        button.update(false, true, TestInstant::ms(20)).event,
+        Some(Event::Press { .. })
+    ));
+}
+
+#[test]
+fn discrepancy_time_is_reported() {
+    let button = new_button();
+
+    assert_eq!(button.discrepancy_time(), DISCREPANCY);
+}
+
+// --- Handler ---
+
+#[derive(Default)]
+struct FaultRecorder(Vec<(bool, bool)>);
+
+impl ButtonHandler<TestInstant> for FaultRecorder {
+    fn on_channel_fault(&mut self, _ctx: &mut HandlerContext<'_, TestInstant>, no: bool, nc: bool) {
+        self.0.push((no, nc));
+    }
+}
+
+#[test]
+fn channel_fault_dispatches_on_channel_fault() {
+    let mut button = new_button();
+    let mut handler = FaultRecorder::default();
+
+    button.update_with(&mut handler, false, false, TestInstant::ms(0));
+    button.update_with(&mut handler, false, false, TestInstant::ms(50));
+
+    assert_eq!(handler.0, [(false, false)]);
+}
@@ -0,0 +1,238 @@
+mod common;
+
+use butt_head::{ButtHead, ButtonState, Config, Event, ServiceTiming};
+use common::{CONFIG, TestDuration, TestInstant, new_button};
+
+static REQUIRE_RELEASE: Config<TestDuration> = Config {
+    require_release_on_enable: true,
+    ..CONFIG
+};
+
+static ACTIVE_LOW: Config<TestDuration> = Config {
+    active_low: true,
+    ..CONFIG
+};
+
+// --- Disable ---
+
+#[test]
+fn disabled_button_emits_nothing() {
+    let mut button = new_button();
+    button.disable();
+
+    let result = button.update(true, TestInstant::ms(0));
+    assert_eq!(result.event, None);
+    assert_eq!(result.next_service, ServiceTiming::Idle);
+    assert_eq!(button.update(true, TestInstant::ms(600)).event, None);
+    assert_eq!(button.update(false, TestInstant::ms(700)).event, None);
+    assert!(!button.is_enabled());
+}
+
+#[test]
+fn disable_discards_pending_click() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.update(false, TestInstant::ms(100));
+
+    button.disable();
+    button.enable(false, TestInstant::ms(200));
+
+    assert_eq!(button.state(), ButtonState::Idle);
+    assert_eq!(button.update(false, TestInstant::ms(400)).event, None);
+}
+
+#[test]
+fn disable_during_press_emits_no_release() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+
+    button.disable();
+    button.update(false, TestInstant::ms(100));
+    button.enable(false, TestInstant::ms(200));
+
+    assert_eq!(button.update(false, TestInstant::ms(300)).event, None);
+    assert_eq!(button.update(false, TestInstant::ms(600)).event, None);
+}
+
+#[test]
+fn disabled_button_tracks_input_level() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.disable();
+    assert!(button.is_pressed());
+
+    button.update(false, TestInstant::ms(100));
+    assert!(!button.is_pressed());
+    button.update(true, TestInstant::ms(200));
+    assert!(button.is_pressed());
+    assert_eq!(button.state(), ButtonState::Idle);
+}
+
+// --- Enable ---
+
+#[test]
+fn enable_while_released_resumes_normally() {
+    let mut button = new_button();
+    button.disable();
+    button.enable(false, TestInstant::ms(1000));
+
+    assert!(button.is_enabled());
+    button.update(true, TestInstant::ms(1100));
+    button.update(false, TestInstant::ms(1200));
+    let result = button.update(false, TestInstant::ms(1500));
+    assert_eq!(result.event, Some(Event::Click { count: 1 }));
+}
+
+#[test]
+fn press_released_while_disabled_is_not_a_phantom_release() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.disable();
+    button.enable(false, TestInstant::ms(500));
+
+    // The input was already released at enable(); nothing to report.
+    let result = button.update(false, TestInstant::ms(600));
+    assert_eq!(result.event, None);
+    assert_eq!(result.next_service, ServiceTiming::Idle);
+}
+
+#[test]
+fn press_held_across_enable_is_adopted() {
+    let mut button = new_button();
+    button.disable();
+    button.update(true, TestInstant::ms(0));
+    button.enable(true, TestInstant::ms(1000));
+
+    let result = button.update(true, TestInstant::ms(1001));
+    assert_eq!(
+        result.event,
+        Some(Event::Press {
+            at: TestInstant::ms(1000)
+        })
+    );
+
+    let result = button.update(false, TestInstant::ms(1100));
+    assert_eq!(
+        result.event,
+        Some(Event::Release {
+            duration: TestDuration(100),
+            click_follows: true,
+        })
+    );
+}
+
+#[test]
+fn adopted_press_holds_from_enable() {
+    let mut button = new_button();
+    button.disable();
+    button.enable(true, TestInstant::ms(1000));
+    button.update(true, TestInstant::ms(1000)); // Press
+
+    assert_eq!(button.update(true, TestInstant::ms(1499)).event, None);
+    let result = button.update(true, TestInstant::ms(1500));
+    assert_eq!(
+        result.event,
+        Some(Event::Hold {
+            clicks_before: 0,
+            level: 0
+        })
+    );
+}
+
+#[test]
+fn require_release_ignores_press_held_across_enable() {
+    let mut button = ButtHead::new(&REQUIRE_RELEASE);
+    button.disable();
+    button.enable(true, TestInstant::ms(1000));
+
+    assert_eq!(
+        button.state(),
+        ButtonState::Suppressed {
+            since: TestInstant::ms(1000)
+        }
+    );
+    assert_eq!(button.update(true, TestInstant::ms(1600)).event, None);
+    assert_eq!(button.update(false, TestInstant::ms(1700)).event, None);
+    assert_eq!(button.update(false, TestInstant::ms(2100)).event, None);
+
+    // Active again after the full release.
+    let result = button.update(true, TestInstant::ms(2200));
+    assert_eq!(
+        result.event,
+        Some(Event::Press {
+            at: TestInstant::ms(2200)
+        })
+    );
+}
+
+#[test]
+fn enable_honours_active_low() {
+    let mut button = ButtHead::new(&ACTIVE_LOW);
+    button.disable();
+    button.enable(true, TestInstant::ms(0)); // pin high = released
+
+    assert!(!button.is_pressed());
+    let result = button.update(false, TestInstant::ms(100));
+    assert_eq!(
+        result.event,
+        Some(Event::Press {
+            at: TestInstant::ms(100)
+        })
+    );
+}
+
+// --- Reset ---
+
+#[test]
+fn reset_discards_pending_click() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.update(false, TestInstant::ms(100));
+
+    button.reset();
+
+    assert_eq!(button.state(), ButtonState::Idle);
+    assert_eq!(button.update(false, TestInstant::ms(400)).event, None);
+}
+
+#[test]
+fn reset_during_press_ignores_it_until_release() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.update(true, TestInstant::ms(500)); // Hold
+
+    button.reset();
+
+    assert_eq!(
+        button.state(),
+        ButtonState::Suppressed {
+            since: TestInstant::ms(0)
+        }
+    );
+    assert_eq!(button.update(true, TestInstant::ms(700)).event, None);
+    assert_eq!(button.update(false, TestInstant::ms(800)).event, None);
+    assert_eq!(button.update(false, TestInstant::ms(801)).event, None);
+}
+
+#[test]
+fn reset_discards_queued_hold_end() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.update(true, TestInstant::ms(500)); // Hold
+    button.update(false, TestInstant::ms(600)); // Release, HoldEnd queued
+
+    button.reset();
+
+    let result = button.update(false, TestInstant::ms(601));
+    assert_eq!(result.event, None);
+    assert_eq!(result.next_service, ServiceTiming::Idle);
+}
+
+#[test]
+fn reset_keeps_button_enabled() {
+    let mut button = new_button();
+    button.reset();
+
+    assert!(button.is_enabled());
+    assert!(button.update(true, TestInstant::ms(0)).event.is_some());
+}
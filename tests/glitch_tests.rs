@@ -0,0 +1,250 @@
+mod common;
+
+use butt_head::{
+    ButtHead, ButtonHandler, ButtonState, Config, Event, HandlerContext, ServiceTiming,
+};
+use common::{CONFIG, TestDuration, TestInstant};
+
+static MIN_PRESS: Config<TestDuration> = Config {
+    min_press_duration: Some(TestDuration(5)),
+    report_glitches: true,
+    ..CONFIG
+};
+
+static SILENT: Config<TestDuration> = Config {
+    min_press_duration: Some(TestDuration(5)),
+    report_glitches: false,
+    ..CONFIG
+};
+
+fn new_button() -> ButtHead<TestInstant> {
+    ButtHead::new(&MIN_PRESS)
+}
+
+// --- Rejection ---
+
+#[test]
+fn short_pulse_is_reported_as_glitch() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+
+    let result = button.update(false, TestInstant::ms(3));
+
+    assert_eq!(
+        result.event,
+        Some(Event::Glitch {
+            duration: TestDuration(3)
+        })
+    );
+    assert_eq!(result.next_service, ServiceTiming::Idle);
+}
+
+#[test]
+fn short_pulse_emits_no_press_or_click() {
+    let mut button = new_button();
+
+    assert_eq!(button.update(true, TestInstant::ms(0)).event, None);
+    button.update(false, TestInstant::ms(2));
+    assert_eq!(button.update(false, TestInstant::ms(1000)).event, None);
+}
+
+#[test]
+fn glitch_is_silent_unless_reported() {
+    let mut button = ButtHead::new(&SILENT);
+    button.update(true, TestInstant::ms(0));
+
+    let result = button.update(false, TestInstant::ms(3));
+
+    assert_eq!(result.event, None);
+    assert_eq!(result.next_service, ServiceTiming::Idle);
+}
+
+#[test]
+fn glitch_does_not_count_toward_multi_click() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.update(true, TestInstant::ms(5)); // Press
+    button.update(false, TestInstant::ms(100)); // Release
+    button.update(true, TestInstant::ms(200));
+    button.update(false, TestInstant::ms(202)); // Glitch
+
+    let result = button.update(false, TestInstant::ms(400));
+
+    assert_eq!(result.event, Some(Event::Click { count: 1 }));
+}
+
+#[test]
+fn glitch_keeps_original_click_timeout() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.update(true, TestInstant::ms(5));
+    button.update(false, TestInstant::ms(100));
+    button.update(true, TestInstant::ms(200));
+
+    let result = button.update(false, TestInstant::ms(202));
+
+    // Click is still due 300ms after the real release at 100ms.
+    assert_eq!(result.next_service, ServiceTiming::Delay(TestDuration(198)));
+    assert_eq!(
+        button.state(),
+        ButtonState::WaitForMultiClick {
+            clicks: 1,
+            since: TestInstant::ms(100)
+        }
+    );
+}
+
+// --- Acceptance ---
+
+#[test]
+fn press_is_emitted_after_min_duration_with_original_instant() {
+    let mut button = new_button();
+
+    let result = button.update(true, TestInstant::ms(0));
+    assert_eq!(result.event, None);
+    assert_eq!(result.next_service, ServiceTiming::Delay(TestDuration(5)));
+
+    let result = button.update(true, TestInstant::ms(5));
+    assert_eq!(
+        result.event,
+        Some(Event::Press {
+            at: TestInstant::ms(0)
+        })
+    );
+    assert_eq!(result.next_service, ServiceTiming::Delay(TestDuration(495)));
+}
+
+#[test]
+fn hold_is_timed_from_original_press() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.update(true, TestInstant::ms(5));
+
+    let result = button.update(true, TestInstant::ms(500));
+
+    assert_eq!(
+        result.event,
+        Some(Event::Hold {
+            clicks_before: 0,
+            level: 0
+        })
+    );
+}
+
+#[test]
+fn late_service_reports_press_then_release() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+
+    // The press outlasted the minimum, but the first call after it is the
+    // release edge.
+    let result = button.update(false, TestInstant::ms(50));
+    assert_eq!(
+        result.event,
+        Some(Event::Press {
+            at: TestInstant::ms(0)
+        })
+    );
+    assert_eq!(result.next_service, ServiceTiming::Immediate);
+
+    let result = button.update(false, TestInstant::ms(50));
+    assert_eq!(
+        result.event,
+        Some(Event::Release {
+            duration: TestDuration(50),
+            click_follows: true,
+        })
+    );
+
+    let result = button.update(false, TestInstant::ms(350));
+    assert_eq!(result.event, Some(Event::Click { count: 1 }));
+}
+
+#[test]
+fn second_press_of_double_click_is_also_qualified() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.update(true, TestInstant::ms(5));
+    button.update(false, TestInstant::ms(100));
+    button.update(true, TestInstant::ms(200));
+    button.update(true, TestInstant::ms(205));
+    button.update(false, TestInstant::ms(300));
+
+    let result = button.update(false, TestInstant::ms(600));
+
+    assert_eq!(result.event, Some(Event::Click { count: 2 }));
+}
+
+// --- Queries ---
+
+#[test]
+fn pending_press_reports_state_and_deadline() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.update(false, TestInstant::ms(100));
+    button.update(true, TestInstant::ms(150));
+
+    assert_eq!(
+        button.state(),
+        ButtonState::PendingPress {
+            since: TestInstant::ms(150),
+            clicks: 1
+        }
+    );
+    assert_eq!(button.pending_click_count(), 1);
+    assert_eq!(
+        button.time_until_next_event(TestInstant::ms(152)),
+        Some(TestDuration(3))
+    );
+}
+
+#[test]
+fn pending_press_can_be_suppressed() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+
+    assert!(button.suppress_until_release());
+    assert_eq!(button.update(true, TestInstant::ms(10)).event, None);
+    assert_eq!(button.update(false, TestInstant::ms(20)).event, None);
+}
+
+#[test]
+fn cancel_gesture_suppresses_pending_press_without_glitch() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+
+    assert!(button.cancel_gesture());
+    assert_eq!(
+        button.state(),
+        ButtonState::Suppressed {
+            since: TestInstant::ms(0)
+        }
+    );
+    // Released before the minimum: neither Press nor Glitch.
+    assert_eq!(button.update(false, TestInstant::ms(2)).event, None);
+    assert_eq!(button.state(), ButtonState::Idle);
+}
+
+// --- Handler ---
+
+#[derive(Default)]
+struct GlitchCounter(Vec<TestDuration>);
+
+impl ButtonHandler<TestInstant> for GlitchCounter {
+    fn on_glitch(&mut self, _ctx: &mut HandlerContext<'_, TestInstant>, duration: TestDuration) {
+        self.0.push(duration);
+    }
+}
+
+#[test]
+fn glitch_dispatches_on_glitch() {
+    let mut button = new_button();
+    let mut handler = GlitchCounter::default();
+
+    button.update_with(&mut handler, true, TestInstant::ms(0));
+    button.update_with(&mut handler, false, TestInstant::ms(1));
+    button.update_with(&mut handler, true, TestInstant::ms(10));
+    button.update_with(&mut handler, false, TestInstant::ms(12));
+
+    assert_eq!(handler.0, [TestDuration(1), TestDuration(2)]);
+}
@@ -0,0 +1,238 @@
+mod common;
+
+use butt_head::{ButtHead, ButtonHandler, Config, HandlerContext, ServiceTiming};
+use common::{CONFIG, TestDuration, TestInstant, new_button};
+
+#[derive(Default)]
+struct Recorder {
+    presses: Vec<TestInstant>,
+    releases: Vec<(TestDuration, bool)>,
+    clicks: Vec<u8>,
+    holds: Vec<(u8, u32)>,
+    hold_ends: Vec<(u8, u32, TestDuration)>,
+    stucks: Vec<TestDuration>,
+    cancel_on_release: bool,
+    cancel_on_hold: bool,
+}
+
+impl ButtonHandler<TestInstant> for Recorder {
+    fn on_press(&mut self, _ctx: &mut HandlerContext<'_, TestInstant>, at: TestInstant) {
+        self.presses.push(at);
+    }
+
+    fn on_release(
+        &mut self,
+        ctx: &mut HandlerContext<'_, TestInstant>,
+        duration: TestDuration,
+        click_follows: bool,
+    ) {
+        self.releases.push((duration, click_follows));
+        if self.cancel_on_release && click_follows {
+            assert!(ctx.cancel_pending_click());
+        }
+    }
+
+    fn on_click(&mut self, _ctx: &mut HandlerContext<'_, TestInstant>, count: u8) {
+        self.clicks.push(count);
+    }
+
+    fn on_hold(
+        &mut self,
+        ctx: &mut HandlerContext<'_, TestInstant>,
+        clicks_before: u8,
+        level: u32,
+    ) {
+        self.holds.push((clicks_before, level));
+        if self.cancel_on_hold {
+            assert!(ctx.cancel_gesture());
+        }
+    }
+
+    fn on_hold_end(
+        &mut self,
+        _ctx: &mut HandlerContext<'_, TestInstant>,
+        clicks_before: u8,
+        levels: u32,
+        duration: TestDuration,
+    ) {
+        self.hold_ends.push((clicks_before, levels, duration));
+    }
+
+    fn on_stuck(&mut self, _ctx: &mut HandlerContext<'_, TestInstant>, duration: TestDuration) {
+        self.stucks.push(duration);
+    }
+}
+
+/// A handler that relies entirely on the default no-op methods.
+struct ClickCounter(u32);
+
+impl ButtonHandler<TestInstant> for ClickCounter {
+    fn on_click(&mut self, _ctx: &mut HandlerContext<'_, TestInstant>, _count: u8) {
+        self.0 += 1;
+    }
+}
+
+static STUCK_CONFIG: Config<TestDuration> = Config {
+    max_press_duration: Some(TestDuration(1000)),
+    ..CONFIG
+};
+
+// --- Dispatch ---
+
+#[test]
+fn click_gesture_dispatches_press_release_and_click() {
+    let mut button = new_button();
+    let mut handler = Recorder::default();
+
+    button.update_with(&mut handler, true, TestInstant::ms(0));
+    button.update_with(&mut handler, false, TestInstant::ms(100));
+    button.update_with(&mut handler, false, TestInstant::ms(400));
+
+    assert_eq!(handler.presses, [TestInstant::ms(0)]);
+    assert_eq!(handler.releases, [(TestDuration(100), true)]);
+    assert_eq!(handler.clicks, [1]);
+}
+
+#[test]
+fn hold_gesture_dispatches_hold_and_hold_end() {
+    let mut button = new_button();
+    let mut handler = Recorder::default();
+
+    button.update_with(&mut handler, true, TestInstant::ms(0));
+    button.update_with(&mut handler, true, TestInstant::ms(500));
+    button.update_with(&mut handler, true, TestInstant::ms(700));
+    button.update_with(&mut handler, false, TestInstant::ms(750));
+    button.update_with(&mut handler, false, TestInstant::ms(751));
+
+    assert_eq!(handler.holds, [(0, 0), (0, 1)]);
+    assert_eq!(handler.releases, [(TestDuration(750), false)]);
+    assert_eq!(handler.hold_ends, [(0, 2, TestDuration(750))]);
+    assert!(handler.clicks.is_empty());
+}
+
+#[test]
+fn stuck_dispatches_on_stuck() {
+    let mut button = ButtHead::new(&STUCK_CONFIG);
+    let mut handler = Recorder::default();
+
+    button.update_with(&mut handler, true, TestInstant::ms(0));
+    button.update_with(&mut handler, true, TestInstant::ms(1000));
+
+    assert_eq!(handler.stucks, [TestDuration(1000)]);
+}
+
+#[test]
+fn update_with_returns_service_timing() {
+    let mut button = new_button();
+    let mut handler = Recorder::default();
+
+    let timing = button.update_with(&mut handler, true, TestInstant::ms(0));
+
+    assert_eq!(timing, ServiceTiming::Delay(TestDuration(500)));
+}
+
+#[test]
+fn default_methods_are_no_ops() {
+    let mut button = new_button();
+    let mut handler = ClickCounter(0);
+
+    button.update_with(&mut handler, true, TestInstant::ms(0));
+    button.update_with(&mut handler, false, TestInstant::ms(100));
+    button.update_with(&mut handler, false, TestInstant::ms(400));
+
+    assert_eq!(handler.0, 1);
+}
+
+// --- Context ---
+
+#[test]
+fn cancel_pending_click_from_on_release_suppresses_click() {
+    let mut button = new_button();
+    let mut handler = Recorder {
+        cancel_on_release: true,
+        ..Recorder::default()
+    };
+
+    button.update_with(&mut handler, true, TestInstant::ms(0));
+    button.update_with(&mut handler, false, TestInstant::ms(100));
+    button.update_with(&mut handler, false, TestInstant::ms(400));
+
+    assert_eq!(handler.releases, [(TestDuration(100), true)]);
+    assert!(handler.clicks.is_empty());
+}
+
+#[test]
+fn cancel_gesture_from_on_hold_stops_holds_and_release() {
+    let mut button = new_button();
+    let mut handler = Recorder {
+        cancel_on_hold: true,
+        ..Recorder::default()
+    };
+
+    button.update_with(&mut handler, true, TestInstant::ms(0));
+    button.update_with(&mut handler, true, TestInstant::ms(500));
+    button.update_with(&mut handler, true, TestInstant::ms(700));
+    button.update_with(&mut handler, false, TestInstant::ms(800));
+    button.update_with(&mut handler, false, TestInstant::ms(801));
+
+    assert_eq!(handler.holds, [(0, 0)]);
+    assert!(handler.releases.is_empty());
+    assert!(handler.hold_ends.is_empty());
+}
+
+#[derive(Default)]
+struct ConfirmRecorder {
+    started: Vec<TestInstant>,
+    progress: Vec<TestDuration>,
+    confirmed: u32,
+    aborted: Vec<TestDuration>,
+}
+
+impl ButtonHandler<TestInstant> for ConfirmRecorder {
+    fn on_confirm_started(&mut self, _ctx: &mut HandlerContext<'_, TestInstant>, at: TestInstant) {
+        self.started.push(at);
+    }
+
+    fn on_confirm_progress(
+        &mut self,
+        _ctx: &mut HandlerContext<'_, TestInstant>,
+        elapsed: TestDuration,
+    ) {
+        self.progress.push(elapsed);
+    }
+
+    fn on_confirmed(&mut self, _ctx: &mut HandlerContext<'_, TestInstant>) {
+        self.confirmed += 1;
+    }
+
+    fn on_confirm_aborted(
+        &mut self,
+        _ctx: &mut HandlerContext<'_, TestInstant>,
+        held_for: TestDuration,
+    ) {
+        self.aborted.push(held_for);
+    }
+}
+
+static CONFIRM_CONFIG: Config<TestDuration> = Config {
+    confirm_duration: Some(TestDuration(1000)),
+    confirm_progress_interval: Some(TestDuration(400)),
+    ..CONFIG
+};
+
+#[test]
+fn confirm_gestures_dispatch_confirm_callbacks() {
+    let mut button = ButtHead::new(&CONFIRM_CONFIG);
+    let mut handler = ConfirmRecorder::default();
+
+    button.update_with(&mut handler, true, TestInstant::ms(0));
+    button.update_with(&mut handler, true, TestInstant::ms(400));
+    button.update_with(&mut handler, false, TestInstant::ms(500));
+    button.update_with(&mut handler, true, TestInstant::ms(600));
+    button.update_with(&mut handler, true, TestInstant::ms(1600));
+
+    assert_eq!(handler.started, [TestInstant::ms(0), TestInstant::ms(600)]);
+    assert_eq!(handler.progress, [TestDuration(400)]);
+    assert_eq!(handler.aborted, [TestDuration(500)]);
+    assert_eq!(handler.confirmed, 1);
+}
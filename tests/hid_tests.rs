@@ -0,0 +1,359 @@
+#![cfg(feature = "hid")]
+
+mod common;
+
+use butt_head::hid::{HidAction, HidKeyboard, HidReport, MOD_LEFT_CTRL};
+use butt_head::{Binding, Event, Gesture, Keymap, Match};
+use common::{TestDuration, TestInstant, new_button};
+
+const KEY_A: u8 = 0x04;
+const KEY_B: u8 = 0x05;
+const KEY_C: u8 = 0x06;
+const VOLUME_UP: u16 = 0x00E9;
+
+static KEYMAP: Keymap<HidAction> = Keymap::new(&[
+    Binding {
+        button: Match::Exactly(0),
+        gesture: Gesture::Click {
+            count: Match::Exactly(1),
+        },
+        action: HidAction::Key {
+            modifiers: 0,
+            keycode: KEY_A,
+        },
+    },
+    Binding {
+        button: Match::Exactly(0),
+        gesture: Gesture::Click {
+            count: Match::Exactly(2),
+        },
+        action: HidAction::Key {
+            modifiers: MOD_LEFT_CTRL,
+            keycode: KEY_C,
+        },
+    },
+    Binding {
+        button: Match::Exactly(0),
+        gesture: Gesture::Hold {
+            clicks_before: Match::Exactly(0),
+            level: Match::Any,
+        },
+        action: HidAction::Key {
+            modifiers: 0,
+            keycode: KEY_B,
+        },
+    },
+    Binding {
+        button: Match::Exactly(1),
+        gesture: Gesture::Press,
+        action: HidAction::Key {
+            modifiers: 0,
+            keycode: KEY_C,
+        },
+    },
+    Binding {
+        button: Match::Exactly(2),
+        gesture: Gesture::Click { count: Match::Any },
+        action: HidAction::Consumer(VOLUME_UP),
+    },
+    Binding {
+        button: Match::Exactly(2),
+        gesture: Gesture::Hold {
+            clicks_before: Match::Any,
+            level: Match::Any,
+        },
+        action: HidAction::Consumer(VOLUME_UP),
+    },
+]);
+
+type TestEvent = Event<TestDuration, TestInstant>;
+
+fn keyboard(bytes: [u8; 8]) -> HidReport {
+    HidReport::Keyboard(bytes)
+}
+
+fn collect(hid: &mut HidKeyboard, button: usize, event: TestEvent) -> Vec<HidReport> {
+    hid.process(&KEYMAP, button, &event).collect()
+}
+
+fn press() -> TestEvent {
+    Event::Press {
+        at: TestInstant::ms(0),
+    }
+}
+
+fn release(click_follows: bool) -> TestEvent {
+    Event::Release {
+        duration: TestDuration(100),
+        click_follows,
+    }
+}
+
+// --- Taps ---
+
+#[test]
+fn click_types_key_press_then_release() {
+    let mut hid = HidKeyboard::new();
+
+    let reports = collect(&mut hid, 0, Event::Click { count: 1 });
+
+    assert_eq!(
+        reports,
+        [
+            keyboard([0, 0, KEY_A, 0, 0, 0, 0, 0]),
+            keyboard([0, 0, 0, 0, 0, 0, 0, 0]),
+        ]
+    );
+}
+
+#[test]
+fn click_with_modifier_sets_modifier_byte() {
+    let mut hid = HidKeyboard::new();
+
+    let reports = collect(&mut hid, 0, Event::Click { count: 2 });
+
+    assert_eq!(
+        reports,
+        [
+            keyboard([MOD_LEFT_CTRL, 0, KEY_C, 0, 0, 0, 0, 0]),
+            keyboard([0, 0, 0, 0, 0, 0, 0, 0]),
+        ]
+    );
+}
+
+#[test]
+fn unmapped_event_produces_no_reports() {
+    let mut hid = HidKeyboard::new();
+
+    assert!(collect(&mut hid, 0, press()).is_empty());
+    assert!(collect(&mut hid, 0, Event::Click { count: 3 }).is_empty());
+    assert!(collect(&mut hid, 5, Event::Click { count: 1 }).is_empty());
+}
+
+#[test]
+fn report_bytes_are_eight_bytes() {
+    let mut hid = HidKeyboard::new();
+
+    let report = hid
+        .process(
+            &KEYMAP,
+            0,
+            &Event::<TestDuration, TestInstant>::Click { count: 1 },
+        )
+        .next()
+        .unwrap();
+
+    assert_eq!(report.as_bytes(), &[0, 0, KEY_A, 0, 0, 0, 0, 0]);
+}
+
+// --- Held keys ---
+
+#[test]
+fn hold_holds_key_until_release() {
+    let mut hid = HidKeyboard::new();
+
+    let reports = collect(
+        &mut hid,
+        0,
+        Event::Hold {
+            clicks_before: 0,
+            level: 0,
+        },
+    );
+    assert_eq!(reports, [keyboard([0, 0, KEY_B, 0, 0, 0, 0, 0])]);
+
+    // Further hold levels keep the key down without new reports.
+    let reports = collect(
+        &mut hid,
+        0,
+        Event::Hold {
+            clicks_before: 0,
+            level: 1,
+        },
+    );
+    assert!(reports.is_empty());
+
+    let reports = collect(&mut hid, 0, release(false));
+    assert_eq!(reports, [keyboard([0, 0, 0, 0, 0, 0, 0, 0])]);
+}
+
+#[test]
+fn release_button_lets_go_after_cancelled_press() {
+    let mut button = new_button();
+    let mut hid = HidKeyboard::new();
+    let event = button.update(true, TestInstant::ms(0)).event.unwrap();
+    hid.process(&KEYMAP, 1, &event);
+    assert_eq!(hid.keyboard_report(), [0, 0, KEY_C, 0, 0, 0, 0, 0]);
+
+    // A cancelled press emits no Release, so nothing reaches `process`.
+    button.cancel_gesture();
+    assert_eq!(button.update(false, TestInstant::ms(100)).event, None);
+    assert_eq!(hid.keyboard_report(), [0, 0, KEY_C, 0, 0, 0, 0, 0]);
+
+    assert_eq!(
+        hid.release_button(1).collect::<Vec<_>>(),
+        [keyboard([0, 0, 0, 0, 0, 0, 0, 0])]
+    );
+    assert_eq!(hid.release_button(1).next(), None);
+}
+
+#[test]
+fn release_lets_go_of_key_and_consumer_usage_of_one_button() {
+    static KEY_THEN_VOLUME: Keymap<HidAction> = Keymap::new(&[
+        Binding {
+            button: Match::Any,
+            gesture: Gesture::Press,
+            action: HidAction::Key {
+                modifiers: 0,
+                keycode: KEY_A,
+            },
+        },
+        Binding {
+            button: Match::Any,
+            gesture: Gesture::Hold {
+                clicks_before: Match::Any,
+                level: Match::Any,
+            },
+            action: HidAction::Consumer(VOLUME_UP),
+        },
+    ]);
+    let mut hid = HidKeyboard::new();
+    hid.process(&KEY_THEN_VOLUME, 0, &press());
+    hid.process(
+        &KEY_THEN_VOLUME,
+        0,
+        &TestEvent::Hold {
+            clicks_before: 0,
+            level: 0,
+        },
+    );
+
+    let reports: Vec<_> = hid.process(&KEY_THEN_VOLUME, 0, &release(false)).collect();
+
+    assert_eq!(
+        reports,
+        [
+            keyboard([0, 0, 0, 0, 0, 0, 0, 0]),
+            HidReport::Consumer([0x00, 0x00]),
+        ]
+    );
+    assert_eq!(hid.consumer_report(), [0, 0]);
+}
+
+#[test]
+fn channel_fault_lets_go_of_held_key() {
+    let mut hid = HidKeyboard::new();
+    collect(&mut hid, 1, press());
+
+    let reports = collect(&mut hid, 1, Event::ChannelFault { no: true, nc: true });
+
+    assert_eq!(reports, [keyboard([0, 0, 0, 0, 0, 0, 0, 0])]);
+}
+
+#[test]
+fn press_mapping_holds_key_while_pressed() {
+    let mut hid = HidKeyboard::new();
+
+    assert_eq!(
+        collect(&mut hid, 1, press()),
+        [keyboard([0, 0, KEY_C, 0, 0, 0, 0, 0])]
+    );
+    assert_eq!(
+        collect(&mut hid, 1, release(true)),
+        [keyboard([0, 0, 0, 0, 0, 0, 0, 0])]
+    );
+}
+
+#[test]
+fn keys_held_by_several_buttons_share_a_report() {
+    let mut hid = HidKeyboard::new();
+    collect(
+        &mut hid,
+        0,
+        Event::Hold {
+            clicks_before: 0,
+            level: 0,
+        },
+    );
+
+    let reports = collect(&mut hid, 1, press());
+    assert_eq!(reports, [keyboard([0, 0, KEY_B, KEY_C, 0, 0, 0, 0])]);
+
+    // Releasing button 0 only lets go of its own key.
+    let reports = collect(&mut hid, 0, release(false));
+    assert_eq!(reports, [keyboard([0, 0, KEY_C, 0, 0, 0, 0, 0])]);
+}
+
+#[test]
+fn tap_while_other_key_held_keeps_held_key() {
+    let mut hid = HidKeyboard::new();
+    collect(&mut hid, 1, press());
+
+    let reports = collect(&mut hid, 0, Event::Click { count: 1 });
+
+    assert_eq!(
+        reports,
+        [
+            keyboard([0, 0, KEY_C, KEY_A, 0, 0, 0, 0]),
+            keyboard([0, 0, KEY_C, 0, 0, 0, 0, 0]),
+        ]
+    );
+}
+
+// --- Consumer control ---
+
+#[test]
+fn consumer_click_sends_usage_then_zero() {
+    let mut hid = HidKeyboard::new();
+
+    let reports = collect(&mut hid, 2, Event::Click { count: 1 });
+
+    assert_eq!(
+        reports,
+        [
+            HidReport::Consumer([0xE9, 0x00]),
+            HidReport::Consumer([0x00, 0x00]),
+        ]
+    );
+}
+
+#[test]
+fn consumer_hold_holds_usage_until_release() {
+    let mut hid = HidKeyboard::new();
+
+    let reports = collect(
+        &mut hid,
+        2,
+        Event::Hold {
+            clicks_before: 0,
+            level: 0,
+        },
+    );
+    assert_eq!(reports, [HidReport::Consumer([0xE9, 0x00])]);
+
+    let reports = collect(&mut hid, 2, release(false));
+    assert_eq!(reports, [HidReport::Consumer([0x00, 0x00])]);
+}
+
+// --- End to end ---
+
+#[test]
+fn button_click_drives_key_tap() {
+    let mut button = new_button();
+    let mut hid = HidKeyboard::new();
+    let mut reports = Vec::new();
+
+    for (level, t) in [(true, 0), (false, 100), (false, 400)] {
+        if let Some(event) = button.update(level, TestInstant::ms(t)).event {
+            reports.extend(hid.process(&KEYMAP, 0, &event));
+        }
+    }
+
+    assert_eq!(
+        reports,
+        [
+            keyboard([0, 0, KEY_A, 0, 0, 0, 0, 0]),
+            keyboard([0, 0, 0, 0, 0, 0, 0, 0]),
+        ]
+    );
+}
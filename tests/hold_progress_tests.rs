@@ -0,0 +1,165 @@
+mod common;
+
+use butt_head::{ButtHead, Config, HoldProgress};
+use common::{CONFIG, TestDuration, TestInstant, new_button};
+
+static STAGES: Config<TestDuration> = Config {
+    hold_stages: &[TestDuration(2000), TestDuration(5000)],
+    ..CONFIG
+};
+
+fn progress(elapsed: u64, total: u64) -> Option<HoldProgress<TestDuration>> {
+    Some(HoldProgress {
+        elapsed: TestDuration(elapsed),
+        total: TestDuration(total),
+    })
+}
+
+// --- hold_progress ---
+
+#[test]
+fn idle_has_no_progress() {
+    let button = new_button();
+
+    assert_eq!(button.hold_progress(TestInstant::ms(0)), None);
+    assert_eq!(button.time_to_next_hold(TestInstant::ms(0)), None);
+}
+
+#[test]
+fn progress_counts_toward_first_hold() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+
+    assert_eq!(button.hold_progress(TestInstant::ms(0)), progress(0, 500));
+    assert_eq!(
+        button.hold_progress(TestInstant::ms(200)),
+        progress(200, 500)
+    );
+}
+
+#[test]
+fn progress_after_hold_targets_next_repeat() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.update(true, TestInstant::ms(500));
+
+    assert_eq!(
+        button.hold_progress(TestInstant::ms(600)),
+        progress(600, 700)
+    );
+}
+
+#[test]
+fn progress_targets_next_stage() {
+    let mut button = ButtHead::new(&STAGES);
+    button.update(true, TestInstant::ms(0));
+    assert_eq!(
+        button.hold_progress(TestInstant::ms(1000)),
+        progress(1000, 2000)
+    );
+
+    button.update(true, TestInstant::ms(2000));
+    assert_eq!(
+        button.hold_progress(TestInstant::ms(3000)),
+        progress(3000, 5000)
+    );
+}
+
+#[test]
+fn progress_ends_after_last_stage() {
+    let mut button = ButtHead::new(&STAGES);
+    button.update(true, TestInstant::ms(0));
+    button.update(true, TestInstant::ms(2000));
+    button.update(true, TestInstant::ms(5000));
+
+    assert_eq!(button.hold_progress(TestInstant::ms(6000)), None);
+}
+
+#[test]
+fn overdue_progress_is_capped_at_total() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+
+    // The hold is due but update() has not run yet.
+    assert_eq!(
+        button.hold_progress(TestInstant::ms(800)),
+        progress(500, 500)
+    );
+    assert_eq!(
+        button.time_to_next_hold(TestInstant::ms(800)),
+        Some(TestDuration(0))
+    );
+}
+
+#[test]
+fn release_clears_progress() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.update(false, TestInstant::ms(100));
+
+    assert_eq!(button.hold_progress(TestInstant::ms(150)), None);
+}
+
+#[test]
+fn cancelled_press_has_no_progress() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.cancel_gesture();
+
+    assert_eq!(button.hold_progress(TestInstant::ms(100)), None);
+}
+
+// --- time_to_next_hold ---
+
+#[test]
+fn time_to_next_hold_counts_down() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+
+    assert_eq!(
+        button.time_to_next_hold(TestInstant::ms(100)),
+        Some(TestDuration(400))
+    );
+    button.update(true, TestInstant::ms(500));
+    assert_eq!(
+        button.time_to_next_hold(TestInstant::ms(550)),
+        Some(TestDuration(150))
+    );
+}
+
+#[test]
+fn time_to_next_hold_ignores_stuck_deadline() {
+    static STUCK: Config<TestDuration> = Config {
+        max_press_duration: Some(TestDuration(300)),
+        ..CONFIG
+    };
+    let mut button = ButtHead::new(&STUCK);
+    button.update(true, TestInstant::ms(0));
+
+    assert_eq!(
+        button.time_until_next_event(TestInstant::ms(0)),
+        Some(TestDuration(300))
+    );
+    assert_eq!(
+        button.time_to_next_hold(TestInstant::ms(0)),
+        Some(TestDuration(500))
+    );
+}
+
+// --- HoldProgress ---
+
+#[test]
+fn permille_scales_elapsed_over_total() {
+    let half = HoldProgress {
+        elapsed: TestDuration(250),
+        total: TestDuration(500),
+    };
+    let done = HoldProgress {
+        elapsed: TestDuration(0),
+        total: TestDuration(0),
+    };
+
+    assert_eq!(half.permille(), 500);
+    assert_eq!(half.remaining(), TestDuration(250));
+    assert_eq!(done.permille(), 1000);
+}
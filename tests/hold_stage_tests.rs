@@ -0,0 +1,197 @@
+mod common;
+
+use butt_head::{ButtHead, ButtonHandler, Config, Event, HandlerContext, ServiceTiming};
+use common::{CONFIG, TestDuration, TestInstant};
+
+/// Menu at 2 s, network reset at 5 s, factory reset at 10 s.
+static STAGES: Config<TestDuration> = Config {
+    hold_stages: &[TestDuration(2000), TestDuration(5000), TestDuration(10_000)],
+    ..CONFIG
+};
+
+fn new_button() -> ButtHead<TestInstant> {
+    ButtHead::new(&STAGES)
+}
+
+fn stage(index: u8, clicks_before: u8) -> Option<Event<TestDuration, TestInstant>> {
+    Some(Event::HoldStage {
+        index,
+        clicks_before,
+    })
+}
+
+// --- Stages ---
+
+#[test]
+fn each_stage_fires_once_at_its_threshold() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+
+    assert_eq!(button.update(true, TestInstant::ms(1999)).event, None);
+    assert_eq!(
+        button.update(true, TestInstant::ms(2000)).event,
+        stage(0, 0)
+    );
+    assert_eq!(button.update(true, TestInstant::ms(4999)).event, None);
+    assert_eq!(
+        button.update(true, TestInstant::ms(5000)).event,
+        stage(1, 0)
+    );
+    assert_eq!(
+        button.update(true, TestInstant::ms(10_000)).event,
+        stage(2, 0)
+    );
+    assert_eq!(button.update(true, TestInstant::ms(60_000)).event, None);
+}
+
+#[test]
+fn no_hold_events_fire_between_stages() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+
+    for t in (100..2000).step_by(100) {
+        assert_eq!(button.update(true, TestInstant::ms(t)).event, None);
+    }
+}
+
+#[test]
+fn stage_carries_clicks_before() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.update(false, TestInstant::ms(100));
+    button.update(true, TestInstant::ms(200));
+
+    assert_eq!(
+        button.update(true, TestInstant::ms(2200)).event,
+        stage(0, 1)
+    );
+}
+
+// --- Scheduling ---
+
+#[test]
+fn press_schedules_first_stage() {
+    let mut button = new_button();
+
+    let result = button.update(true, TestInstant::ms(0));
+
+    assert_eq!(
+        result.next_service,
+        ServiceTiming::Delay(TestDuration(2000))
+    );
+}
+
+#[test]
+fn stage_schedules_precisely_to_next_stage() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+
+    // Serviced 500ms late; the next stage is still due at 5000ms.
+    let result = button.update(true, TestInstant::ms(2500));
+    assert_eq!(result.event, stage(0, 0));
+    assert_eq!(
+        result.next_service,
+        ServiceTiming::Delay(TestDuration(2500))
+    );
+}
+
+#[test]
+fn last_stage_leaves_nothing_scheduled() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.update(true, TestInstant::ms(2000));
+    button.update(true, TestInstant::ms(5000));
+
+    let result = button.update(true, TestInstant::ms(10_000));
+
+    assert_eq!(result.next_service, ServiceTiming::Idle);
+    assert_eq!(button.time_until_next_event(TestInstant::ms(10_000)), None);
+}
+
+#[test]
+fn time_until_next_event_counts_down_to_stage() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.update(true, TestInstant::ms(2000));
+
+    assert_eq!(
+        button.time_until_next_event(TestInstant::ms(3000)),
+        Some(TestDuration(2000))
+    );
+}
+
+// --- Release ---
+
+#[test]
+fn release_before_first_stage_is_a_click() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+
+    // Past hold_delay, but hold_delay no longer applies.
+    let result = button.update(false, TestInstant::ms(1500));
+    assert_eq!(
+        result.event,
+        Some(Event::Release {
+            duration: TestDuration(1500),
+            click_follows: true,
+        })
+    );
+
+    let result = button.update(false, TestInstant::ms(1800));
+    assert_eq!(result.event, Some(Event::Click { count: 1 }));
+}
+
+#[test]
+fn release_after_stage_ends_hold() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.update(true, TestInstant::ms(2000));
+    button.update(true, TestInstant::ms(5000));
+
+    let result = button.update(false, TestInstant::ms(6000));
+    assert_eq!(
+        result.event,
+        Some(Event::Release {
+            duration: TestDuration(6000),
+            click_follows: false,
+        })
+    );
+
+    let result = button.update(false, TestInstant::ms(6001));
+    assert_eq!(
+        result.event,
+        Some(Event::HoldEnd {
+            clicks_before: 0,
+            levels: 2,
+            duration: TestDuration(6000),
+        })
+    );
+}
+
+// --- Handler ---
+
+#[derive(Default)]
+struct StageRecorder(Vec<(u8, u8)>);
+
+impl ButtonHandler<TestInstant> for StageRecorder {
+    fn on_hold_stage(
+        &mut self,
+        _ctx: &mut HandlerContext<'_, TestInstant>,
+        index: u8,
+        clicks_before: u8,
+    ) {
+        self.0.push((index, clicks_before));
+    }
+}
+
+#[test]
+fn stages_dispatch_on_hold_stage() {
+    let mut button = new_button();
+    let mut handler = StageRecorder::default();
+
+    button.update_with(&mut handler, true, TestInstant::ms(0));
+    button.update_with(&mut handler, true, TestInstant::ms(2000));
+    button.update_with(&mut handler, true, TestInstant::ms(5000));
+
+    assert_eq!(handler.0, [(0, 0), (1, 0)]);
+}
@@ -87,6 +87,101 @@ fn release_after_hold_emits_release_with_correct_duration() {
     );
 }
 
+#[test]
+fn hold_level_does_not_saturate_at_u8_max() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+
+    // Service every hold up to level 299
+    for level in 0..300 {
+        button.update(true, TestInstant::ms(500 + level * 200));
+    }
+    let result = button.update(true, TestInstant::ms(500 + 300 * 200));
+
+    assert_eq!(
+        result.event,
+        Some(Event::Hold {
+            clicks_before: 0,
+            level: 300
+        })
+    );
+}
+
+// --- Hold end ---
+
+#[test]
+fn hold_end_follows_release_after_hold() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.update(true, TestInstant::ms(500)); // level 0
+    button.update(true, TestInstant::ms(700)); // level 1
+    button.update(false, TestInstant::ms(800)); // Release
+
+    let result = button.update(false, TestInstant::ms(801));
+
+    assert_eq!(
+        result.event,
+        Some(Event::HoldEnd {
+            clicks_before: 0,
+            levels: 2,
+            duration: TestDuration(800),
+        })
+    );
+}
+
+#[test]
+fn hold_end_carries_clicks_before() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.update(false, TestInstant::ms(100));
+    button.update(true, TestInstant::ms(200));
+    button.update(true, TestInstant::ms(700)); // Hold, clicks_before = 1
+    button.update(false, TestInstant::ms(900)); // Release
+
+    let result = button.update(false, TestInstant::ms(901));
+
+    assert_eq!(
+        result.event,
+        Some(Event::HoldEnd {
+            clicks_before: 1,
+            levels: 1,
+            duration: TestDuration(700),
+        })
+    );
+}
+
+#[test]
+fn hold_end_not_emitted_after_click() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.update(false, TestInstant::ms(100));
+
+    let result = button.update(false, TestInstant::ms(400));
+
+    assert_eq!(result.event, Some(Event::Click { count: 1 }));
+}
+
+#[test]
+fn press_during_pending_hold_end_is_not_lost() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.update(true, TestInstant::ms(500)); // Hold
+    button.update(false, TestInstant::ms(600)); // Release, HoldEnd pending
+
+    // Caller skipped the immediate follow-up and the button is pressed again.
+    let result = button.update(true, TestInstant::ms(650));
+    assert!(matches!(result.event, Some(Event::HoldEnd { .. })));
+    assert_eq!(result.next_service, ServiceTiming::Immediate);
+
+    let result = button.update(true, TestInstant::ms(651));
+    assert_eq!(
+        result.event,
+        Some(Event::Press {
+            at: TestInstant::ms(650)
+        })
+    );
+}
+
 // --- Click-and-hold ---
 
 #[test]
@@ -141,6 +236,7 @@ fn click_then_hold_does_not_emit_click_after_release() {
     button.update(true, TestInstant::ms(200));
     button.update(true, TestInstant::ms(700)); // hold fires
     button.update(false, TestInstant::ms(900)); // release
+    button.update(false, TestInstant::ms(900)); // HoldEnd
 
     // No click should emerge after the timeout
     let result = button.update(false, TestInstant::ms(1300));
@@ -178,12 +274,24 @@ fn remaining_time_decreases_as_time_advances_while_pressed() {
 }
 
 #[test]
-fn release_after_hold_returns_idle_timing() {
+fn release_after_hold_returns_immediate_timing() {
     let mut button = new_button();
     button.update(true, TestInstant::ms(0));
     button.update(true, TestInstant::ms(500));
 
     let result = button.update(false, TestInstant::ms(600));
 
+    assert_eq!(result.next_service, ServiceTiming::Immediate);
+}
+
+#[test]
+fn hold_end_returns_idle_timing() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.update(true, TestInstant::ms(500));
+    button.update(false, TestInstant::ms(600));
+
+    let result = button.update(false, TestInstant::ms(601));
+
     assert_eq!(result.next_service, ServiceTiming::Idle);
 }
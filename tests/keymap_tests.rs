@@ -0,0 +1,194 @@
+mod common;
+
+use butt_head::{Binding, Event, Gesture, Keymap, Match};
+use common::{TestDuration, TestInstant, new_button};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    Select,
+    Pairing,
+    VolumeUp,
+    FactoryReset,
+    Any,
+}
+
+static KEYMAP: Keymap<Action> = Keymap::new(&[
+    Binding {
+        button: Match::Exactly(0),
+        gesture: Gesture::Click {
+            count: Match::Exactly(1),
+        },
+        action: Action::Select,
+    },
+    Binding {
+        button: Match::Exactly(2),
+        gesture: Gesture::Click {
+            count: Match::Between(2, 3),
+        },
+        action: Action::Pairing,
+    },
+    Binding {
+        button: Match::Exactly(0),
+        gesture: Gesture::Hold {
+            clicks_before: Match::Exactly(0),
+            level: Match::Between(0, 9),
+        },
+        action: Action::VolumeUp,
+    },
+    Binding {
+        button: Match::Exactly(0),
+        gesture: Gesture::Hold {
+            clicks_before: Match::Any,
+            level: Match::AtLeast(10),
+        },
+        action: Action::FactoryReset,
+    },
+    Binding {
+        button: Match::Any,
+        gesture: Gesture::Click { count: Match::Any },
+        action: Action::Any,
+    },
+]);
+
+type TestEvent = Event<TestDuration, TestInstant>;
+
+fn hold(clicks_before: u8, level: u32) -> TestEvent {
+    Event::Hold {
+        clicks_before,
+        level,
+    }
+}
+
+// --- Match ---
+
+#[test]
+fn match_patterns() {
+    assert!(Match::Any.matches(&7u8));
+    assert!(Match::Exactly(7u8).matches(&7));
+    assert!(!Match::Exactly(7u8).matches(&8));
+    assert!(Match::Between(2u8, 4).matches(&2));
+    assert!(Match::Between(2u8, 4).matches(&4));
+    assert!(!Match::Between(2u8, 4).matches(&5));
+    assert!(Match::AtLeast(10u32).matches(&1000));
+    assert!(!Match::AtLeast(10u32).matches(&9));
+}
+
+// --- lookup ---
+
+#[test]
+fn lookup_exact_click() {
+    let event: TestEvent = Event::Click { count: 1 };
+    assert_eq!(KEYMAP.lookup(0, &event), Some(&Action::Select));
+}
+
+#[test]
+fn lookup_click_count_range() {
+    let double: TestEvent = Event::Click { count: 2 };
+    let triple: TestEvent = Event::Click { count: 3 };
+    assert_eq!(KEYMAP.lookup(2, &double), Some(&Action::Pairing));
+    assert_eq!(KEYMAP.lookup(2, &triple), Some(&Action::Pairing));
+}
+
+#[test]
+fn lookup_hold_level_ranges() {
+    assert_eq!(KEYMAP.lookup(0, &hold(0, 0)), Some(&Action::VolumeUp));
+    assert_eq!(KEYMAP.lookup(0, &hold(0, 9)), Some(&Action::VolumeUp));
+    assert_eq!(KEYMAP.lookup(0, &hold(0, 10)), Some(&Action::FactoryReset));
+    assert_eq!(KEYMAP.lookup(0, &hold(3, 10)), Some(&Action::FactoryReset));
+    assert_eq!(KEYMAP.lookup(0, &hold(1, 0)), None);
+}
+
+#[test]
+fn lookup_first_binding_wins() {
+    // Button 0 single click matches both `Select` and the wildcard binding.
+    let event: TestEvent = Event::Click { count: 1 };
+    assert_eq!(KEYMAP.lookup(0, &event), Some(&Action::Select));
+}
+
+#[test]
+fn lookup_button_wildcard() {
+    let event: TestEvent = Event::Click { count: 5 };
+    assert_eq!(KEYMAP.lookup(7, &event), Some(&Action::Any));
+}
+
+#[test]
+fn lookup_no_match_returns_none() {
+    let event: TestEvent = Event::Press {
+        at: TestInstant::ms(0),
+    };
+    assert_eq!(KEYMAP.lookup(0, &event), None);
+}
+
+#[test]
+fn lookup_on_update_result() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.update(false, TestInstant::ms(100));
+    let result = button.update(false, TestInstant::ms(400));
+
+    let action = result.event.and_then(|event| KEYMAP.lookup(0, &event));
+
+    assert_eq!(action, Some(&Action::Select));
+}
+
+#[test]
+fn hold_stage_gesture_matches_index_and_clicks() {
+    let gesture = Gesture::HoldStage {
+        index: Match::Exactly(2),
+        clicks_before: Match::Any,
+    };
+
+    let stage_2: TestEvent = Event::HoldStage {
+        index: 2,
+        clicks_before: 1,
+    };
+    let stage_1: TestEvent = Event::HoldStage {
+        index: 1,
+        clicks_before: 1,
+    };
+    assert!(gesture.matches(&stage_2));
+    assert!(!gesture.matches(&stage_1));
+    assert!(!gesture.matches(&hold(0, 2)));
+}
+
+#[test]
+fn glitch_gesture_matches_any_duration() {
+    let glitch: TestEvent = Event::Glitch {
+        duration: TestDuration(3),
+    };
+
+    assert!(Gesture::Glitch.matches(&glitch));
+    assert!(!Gesture::Press.matches(&glitch));
+}
+
+#[test]
+fn confirm_gestures_match_their_events() {
+    let started: TestEvent = Event::ConfirmStarted {
+        at: TestInstant::ms(0),
+    };
+    let progress: TestEvent = Event::ConfirmProgress {
+        elapsed: TestDuration(400),
+    };
+    let confirmed: TestEvent = Event::Confirmed;
+    let aborted: TestEvent = Event::ConfirmAborted {
+        held_for: TestDuration(1200),
+    };
+
+    assert!(Gesture::ConfirmStarted.matches(&started));
+    assert!(!Gesture::ConfirmStarted.matches(&progress));
+    assert!(Gesture::ConfirmProgress.matches(&progress));
+    assert!(Gesture::Confirmed.matches(&confirmed));
+    assert!(!Gesture::Confirmed.matches(&aborted));
+    assert!(Gesture::ConfirmAborted.matches(&aborted));
+}
+
+#[test]
+fn channel_fault_gesture_matches_any_levels() {
+    let fault: TestEvent = Event::ChannelFault {
+        no: false,
+        nc: false,
+    };
+
+    assert!(Gesture::ChannelFault.matches(&fault));
+    assert!(!Gesture::Stuck.matches(&fault));
+}
@@ -0,0 +1,190 @@
+#![cfg(feature = "midi")]
+
+mod common;
+
+use butt_head::Event;
+use butt_head::midi::{HoldRamp, MidiConfig, PressMapping};
+use common::{TestDuration, TestInstant, new_button};
+
+type TestEvent = Event<TestDuration, TestInstant>;
+
+const NOTE_CONFIG: MidiConfig = MidiConfig {
+    channel: 2,
+    velocity: 100,
+    press: PressMapping::Note { note: 60 },
+    click_program: Some(10),
+    hold_ramp: Some(HoldRamp {
+        controller: 7,
+        start: 20,
+        step: 30,
+    }),
+};
+
+const CC_CONFIG: MidiConfig = MidiConfig {
+    channel: 0,
+    velocity: 64,
+    press: PressMapping::ControlChange {
+        controller: 64,
+        on: 127,
+        off: 0,
+    },
+    click_program: None,
+    hold_ramp: None,
+};
+
+fn bytes(config: &MidiConfig, event: TestEvent) -> Option<Vec<u8>> {
+    config.translate(&event).map(|m| m.as_bytes().to_vec())
+}
+
+fn press() -> TestEvent {
+    Event::Press {
+        at: TestInstant::ms(0),
+    }
+}
+
+fn release() -> TestEvent {
+    Event::Release {
+        duration: TestDuration(100),
+        click_follows: true,
+    }
+}
+
+fn hold(level: u32) -> TestEvent {
+    Event::Hold {
+        clicks_before: 0,
+        level,
+    }
+}
+
+// --- Press / release ---
+
+#[test]
+fn press_sends_note_on_with_channel_and_velocity() {
+    assert_eq!(bytes(&NOTE_CONFIG, press()), Some(vec![0x92, 60, 100]));
+}
+
+#[test]
+fn release_sends_note_off() {
+    assert_eq!(bytes(&NOTE_CONFIG, release()), Some(vec![0x82, 60, 100]));
+}
+
+#[test]
+fn stuck_sends_note_off() {
+    let event = Event::Stuck {
+        duration: TestDuration(10_000),
+    };
+    assert_eq!(bytes(&NOTE_CONFIG, event), Some(vec![0x82, 60, 100]));
+}
+
+#[test]
+fn channel_fault_sends_note_off() {
+    let event = Event::ChannelFault { no: true, nc: true };
+    assert_eq!(bytes(&NOTE_CONFIG, event), Some(vec![0x82, 60, 100]));
+}
+
+#[test]
+fn release_message_matches_release() {
+    assert_eq!(
+        NOTE_CONFIG.release_message(),
+        NOTE_CONFIG.translate(&release())
+    );
+    assert_eq!(
+        CC_CONFIG.release_message().map(|m| m.as_bytes().to_vec()),
+        Some(vec![0xB0, 64, 0])
+    );
+}
+
+#[test]
+fn release_message_is_none_without_press_mapping() {
+    let config = MidiConfig {
+        press: PressMapping::None,
+        ..NOTE_CONFIG
+    };
+    assert_eq!(config.release_message(), None);
+}
+
+#[test]
+fn press_and_release_send_control_change() {
+    assert_eq!(bytes(&CC_CONFIG, press()), Some(vec![0xB0, 64, 127]));
+    assert_eq!(bytes(&CC_CONFIG, release()), Some(vec![0xB0, 64, 0]));
+}
+
+// --- Click ---
+
+#[test]
+fn click_sends_program_change_offset_by_count() {
+    assert_eq!(
+        bytes(&NOTE_CONFIG, Event::Click { count: 1 }),
+        Some(vec![0xC2, 10])
+    );
+    assert_eq!(
+        bytes(&NOTE_CONFIG, Event::Click { count: 3 }),
+        Some(vec![0xC2, 12])
+    );
+}
+
+#[test]
+fn click_without_program_sends_nothing() {
+    assert_eq!(bytes(&CC_CONFIG, Event::Click { count: 1 }), None);
+}
+
+// --- Hold ---
+
+#[test]
+fn hold_ramps_control_change_by_level() {
+    assert_eq!(bytes(&NOTE_CONFIG, hold(0)), Some(vec![0xB2, 7, 20]));
+    assert_eq!(bytes(&NOTE_CONFIG, hold(2)), Some(vec![0xB2, 7, 80]));
+}
+
+#[test]
+fn hold_ramp_saturates_at_127() {
+    assert_eq!(bytes(&NOTE_CONFIG, hold(5)), Some(vec![0xB2, 7, 127]));
+    assert_eq!(
+        bytes(&NOTE_CONFIG, hold(u32::MAX)),
+        Some(vec![0xB2, 7, 127])
+    );
+}
+
+#[test]
+fn hold_without_ramp_sends_nothing() {
+    assert_eq!(bytes(&CC_CONFIG, hold(0)), None);
+}
+
+// --- End to end ---
+
+#[test]
+fn button_click_produces_note_on_off_and_program_change() {
+    let mut button = new_button();
+    let mut messages = Vec::new();
+
+    for (level, t) in [(true, 0), (false, 100), (false, 400)] {
+        if let Some(event) = button.update(level, TestInstant::ms(t)).event
+            && let Some(message) = NOTE_CONFIG.translate(&event)
+        {
+            messages.push(message.as_bytes().to_vec());
+        }
+    }
+
+    assert_eq!(
+        messages,
+        [vec![0x92, 60, 100], vec![0x82, 60, 100], vec![0xC2, 10]]
+    );
+}
+
+#[test]
+fn cancelled_press_is_ended_with_release_message() {
+    let mut button = new_button();
+    let event = button.update(true, TestInstant::ms(0)).event.unwrap();
+    assert_eq!(
+        NOTE_CONFIG.translate(&event).map(|m| m.as_bytes().to_vec()),
+        Some(vec![0x92, 60, 100])
+    );
+
+    // A cancelled press emits no Release, so the Note Off must be sent here.
+    button.cancel_gesture();
+    assert_eq!(button.update(false, TestInstant::ms(100)).event, None);
+    assert_eq!(
+        NOTE_CONFIG.release_message().map(|m| m.as_bytes().to_vec()),
+        Some(vec![0x82, 60, 100])
+    );
+}
@@ -0,0 +1,267 @@
+mod common;
+
+use butt_head::{Config, RadioEvent, RadioGroup, SelectOn, ServiceTiming, TieBreak};
+use common::{CONFIG, TestDuration, TestInstant};
+
+static MIN_PRESS: Config<TestDuration> = Config {
+    min_press_duration: Some(TestDuration(20)),
+    ..CONFIG
+};
+
+fn selected(index: usize, previous: Option<usize>) -> Option<RadioEvent> {
+    Some(RadioEvent::Selected { index, previous })
+}
+
+fn group(select_on: SelectOn, tie_break: TieBreak) -> RadioGroup<TestInstant, 3> {
+    RadioGroup::new(&CONFIG, select_on, tie_break)
+}
+
+// --- Selection ---
+
+#[test]
+fn nothing_is_selected_initially() {
+    let group = group(SelectOn::Press, TieBreak::LowestIndex);
+
+    assert_eq!(group.selected(), None);
+}
+
+#[test]
+fn press_selects_button() {
+    let mut group = group(SelectOn::Press, TieBreak::LowestIndex);
+
+    let result = group.update([false, true, false], TestInstant::ms(0));
+
+    assert_eq!(result.event, selected(1, None));
+    assert_eq!(group.selected(), Some(1));
+}
+
+#[test]
+fn selecting_another_button_reports_previous() {
+    let mut group = group(SelectOn::Press, TieBreak::LowestIndex);
+    group.update([false, true, false], TestInstant::ms(0));
+    group.update([false, false, false], TestInstant::ms(100));
+
+    let result = group.update([false, false, true], TestInstant::ms(200));
+
+    assert_eq!(result.event, selected(2, Some(1)));
+    assert_eq!(group.selected(), Some(2));
+}
+
+#[test]
+fn reselecting_selected_button_emits_nothing() {
+    let mut group = group(SelectOn::Press, TieBreak::LowestIndex);
+    group.update([true, false, false], TestInstant::ms(0));
+    group.update([false, false, false], TestInstant::ms(100));
+
+    assert_eq!(
+        group
+            .update([true, false, false], TestInstant::ms(200))
+            .event,
+        None
+    );
+    assert_eq!(group.selected(), Some(0));
+}
+
+#[test]
+fn click_selects_after_click_timeout() {
+    let mut group = group(SelectOn::Click, TieBreak::LowestIndex);
+
+    assert_eq!(
+        group.update([false, true, false], TestInstant::ms(0)).event,
+        None
+    );
+    let result = group.update([false, false, false], TestInstant::ms(100));
+    assert_eq!(result.event, None);
+    assert_eq!(result.next_service, ServiceTiming::Delay(TestDuration(300)));
+
+    assert_eq!(
+        group
+            .update([false, false, false], TestInstant::ms(400))
+            .event,
+        selected(1, None)
+    );
+}
+
+#[test]
+fn hold_selects_on_first_hold_only() {
+    let mut group = group(SelectOn::Hold, TieBreak::LowestIndex);
+    group.update([true, false, false], TestInstant::ms(0));
+
+    assert_eq!(
+        group
+            .update([true, false, false], TestInstant::ms(500))
+            .event,
+        selected(0, None)
+    );
+    group.clear();
+    assert_eq!(
+        group
+            .update([true, false, false], TestInstant::ms(700))
+            .event,
+        None
+    );
+}
+
+#[test]
+fn hold_ignores_clicks() {
+    let mut group = group(SelectOn::Hold, TieBreak::LowestIndex);
+    group.update([true, false, false], TestInstant::ms(0));
+    group.update([false, false, false], TestInstant::ms(100));
+
+    assert_eq!(
+        group
+            .update([false, false, false], TestInstant::ms(400))
+            .event,
+        None
+    );
+    assert_eq!(group.selected(), None);
+}
+
+#[test]
+fn hold_selects_on_first_hold_stage() {
+    static STAGES: Config<TestDuration> = Config {
+        hold_stages: &[TestDuration(1000), TestDuration(3000)],
+        ..CONFIG
+    };
+    let mut group: RadioGroup<TestInstant, 2> =
+        RadioGroup::new(&STAGES, SelectOn::Hold, TieBreak::LowestIndex);
+    group.update([false, true], TestInstant::ms(0));
+
+    assert_eq!(
+        group.update([false, true], TestInstant::ms(1000)).event,
+        selected(1, None)
+    );
+}
+
+// --- Tie-break ---
+
+#[test]
+fn simultaneous_presses_go_to_lowest_index() {
+    let mut group = group(SelectOn::Press, TieBreak::LowestIndex);
+
+    assert_eq!(
+        group.update([false, true, true], TestInstant::ms(0)).event,
+        selected(1, None)
+    );
+}
+
+#[test]
+fn first_pressed_with_equal_instants_goes_to_lowest_index() {
+    let mut group = group(SelectOn::Press, TieBreak::FirstPressed);
+
+    assert_eq!(
+        group.update([true, true, true], TestInstant::ms(0)).event,
+        selected(0, None)
+    );
+}
+
+#[test]
+fn first_pressed_wins_when_presses_confirm_together() {
+    let mut group: RadioGroup<TestInstant, 3> =
+        RadioGroup::new(&MIN_PRESS, SelectOn::Press, TieBreak::FirstPressed);
+    group.update([false, false, true], TestInstant::ms(0));
+    group.update([false, true, true], TestInstant::ms(5));
+
+    // Both presses are confirmed by the same update; button 2 was first.
+    assert_eq!(
+        group.update([false, true, true], TestInstant::ms(30)).event,
+        selected(2, None)
+    );
+}
+
+#[test]
+fn lowest_index_ignores_press_order() {
+    let mut group: RadioGroup<TestInstant, 3> =
+        RadioGroup::new(&MIN_PRESS, SelectOn::Press, TieBreak::LowestIndex);
+    group.update([false, false, true], TestInstant::ms(0));
+    group.update([false, true, true], TestInstant::ms(5));
+
+    assert_eq!(
+        group.update([false, true, true], TestInstant::ms(30)).event,
+        selected(1, None)
+    );
+}
+
+#[test]
+fn first_pressed_decides_simultaneous_clicks() {
+    let mut group = group(SelectOn::Click, TieBreak::FirstPressed);
+    group.update([false, true, false], TestInstant::ms(0));
+    group.update([true, true, false], TestInstant::ms(10));
+    group.update([false, false, false], TestInstant::ms(100));
+
+    assert_eq!(
+        group
+            .update([false, false, false], TestInstant::ms(400))
+            .event,
+        selected(1, None)
+    );
+}
+
+// --- Programmatic selection ---
+
+#[test]
+fn select_sets_selection_and_reports_previous() {
+    let mut group = group(SelectOn::Press, TieBreak::LowestIndex);
+
+    assert_eq!(group.select(2), selected(2, None));
+    assert_eq!(group.select(0), selected(0, Some(2)));
+    assert_eq!(group.select(0), None);
+    assert_eq!(group.select(3), None);
+    assert_eq!(group.selected(), Some(0));
+}
+
+#[test]
+fn clear_removes_selection_silently() {
+    let mut group = group(SelectOn::Press, TieBreak::LowestIndex);
+    group.select(1);
+
+    group.clear();
+
+    assert_eq!(group.selected(), None);
+    assert_eq!(
+        group.update([false, true, false], TestInstant::ms(0)).event,
+        selected(1, None)
+    );
+}
+
+// --- Buttons and scheduling ---
+
+#[test]
+fn next_service_is_soonest_of_all_buttons() {
+    let mut group = group(SelectOn::Click, TieBreak::LowestIndex);
+    group.update([true, false, false], TestInstant::ms(0));
+    group.update([false, false, false], TestInstant::ms(100));
+
+    // Button 0 waits for its click timeout (until 400), button 1 for its
+    // hold (until 650).
+    let result = group.update([false, true, false], TestInstant::ms(150));
+
+    assert_eq!(result.next_service, ServiceTiming::Delay(TestDuration(250)));
+}
+
+#[test]
+fn idle_group_needs_no_service() {
+    let mut group = group(SelectOn::Press, TieBreak::LowestIndex);
+
+    let result = group.update([false, false, false], TestInstant::ms(0));
+
+    assert_eq!(result.next_service, ServiceTiming::Idle);
+}
+
+#[test]
+fn buttons_are_exposed_by_index() {
+    static ACTIVE_LOW: Config<TestDuration> = Config {
+        active_low: true,
+        ..CONFIG
+    };
+    let mut group: RadioGroup<TestInstant, 2> =
+        RadioGroup::new(&ACTIVE_LOW, SelectOn::Press, TieBreak::LowestIndex);
+
+    assert_eq!(
+        group.update([true, false], TestInstant::ms(0)).event,
+        selected(1, None)
+    );
+    assert!(group.button(1).unwrap().is_pressed());
+    assert!(!group.button(0).unwrap().is_pressed());
+    assert!(group.button(2).is_none());
+}
@@ -0,0 +1,205 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use butt_head::{Config, Event, ServiceTiming, UpdateResult};
+use common::{CONFIG, TestDuration, TestInstant, new_button};
+use serde::{Serialize, de::DeserializeOwned};
+
+type TestEvent = Event<TestDuration, TestInstant>;
+
+fn round_trip<T: Serialize + DeserializeOwned>(value: &T) -> T {
+    let mut buf = [0u8; 64];
+    let bytes = postcard::to_slice(value, &mut buf).unwrap();
+    postcard::from_bytes(bytes).unwrap()
+}
+
+fn encode<T: Serialize>(value: &T) -> Vec<u8> {
+    let mut buf = [0u8; 64];
+    postcard::to_slice(value, &mut buf).unwrap().to_vec()
+}
+
+// --- Round trips ---
+
+#[test]
+fn every_event_variant_round_trips() {
+    let events: [TestEvent; 13] = [
+        Event::Press {
+            at: TestInstant(1_234),
+        },
+        Event::Release {
+            duration: TestDuration(80),
+            click_follows: true,
+        },
+        Event::Click { count: 3 },
+        Event::Hold {
+            clicks_before: 1,
+            level: 70_000,
+        },
+        Event::HoldEnd {
+            clicks_before: 2,
+            levels: 5,
+            duration: TestDuration(1_500),
+        },
+        Event::Stuck {
+            duration: TestDuration(60_000),
+        },
+        Event::Glitch {
+            duration: TestDuration(2),
+        },
+        Event::HoldStage {
+            index: 2,
+            clicks_before: 1,
+        },
+        Event::ConfirmStarted { at: TestInstant(7) },
+        Event::ConfirmProgress {
+            elapsed: TestDuration(1_000),
+        },
+        Event::Confirmed,
+        Event::ConfirmAborted {
+            held_for: TestDuration(1_200),
+        },
+        Event::ChannelFault {
+            no: true,
+            nc: false,
+        },
+    ];
+
+    for event in events {
+        assert_eq!(round_trip(&event), event);
+    }
+}
+
+#[test]
+fn every_service_timing_variant_round_trips() {
+    let timings = [
+        ServiceTiming::Immediate,
+        ServiceTiming::Delay(TestDuration(300)),
+        ServiceTiming::Idle,
+    ];
+
+    for timing in timings {
+        assert_eq!(round_trip(&timing), timing);
+    }
+}
+
+#[test]
+fn update_result_round_trips() {
+    let mut button = new_button();
+    let result = button.update(true, TestInstant::ms(0));
+
+    let decoded: UpdateResult<TestDuration, TestInstant> = round_trip(&result);
+
+    assert_eq!(decoded.event, result.event);
+    assert_eq!(decoded.next_service, result.next_service);
+}
+
+#[test]
+fn config_round_trips() {
+    let config = Config {
+        max_click_count: Some(2),
+        max_press_duration: Some(TestDuration(10_000)),
+        ..CONFIG
+    };
+
+    let decoded: Config<TestDuration> = round_trip(&config);
+
+    assert_eq!(decoded.active_low, config.active_low);
+    assert_eq!(decoded.click_timeout, config.click_timeout);
+    assert_eq!(decoded.hold_delay, config.hold_delay);
+    assert_eq!(decoded.hold_interval, config.hold_interval);
+    assert_eq!(decoded.max_click_count, config.max_click_count);
+    assert_eq!(decoded.max_press_duration, config.max_press_duration);
+}
+
+#[test]
+fn deserialized_config_drives_a_button() {
+    // Settings loaded at startup live for the rest of the program.
+    let config: &'static Config<TestDuration> = Box::leak(Box::new(round_trip(&CONFIG)));
+    let mut button = butt_head::ButtHead::new(config);
+
+    button.update(true, TestInstant::ms(0));
+    button.update(false, TestInstant::ms(100));
+    let result = button.update(false, TestInstant::ms(400));
+
+    assert_eq!(result.event, Some(Event::Click { count: 1 }));
+}
+
+#[test]
+fn hold_stages_are_not_serialized() {
+    static STAGES: Config<TestDuration> = Config {
+        hold_stages: &[TestDuration(2000)],
+        ..CONFIG
+    };
+
+    let decoded: Config<TestDuration> = round_trip(&STAGES);
+
+    assert!(decoded.hold_stages.is_empty());
+    assert_eq!(encode(&STAGES), encode(&CONFIG));
+}
+
+// --- Wire format ---
+
+#[test]
+fn event_wire_format_is_variant_index_then_fields() {
+    assert_eq!(
+        encode(&TestEvent::Press {
+            at: TestInstant(300)
+        }),
+        [0, 0xAC, 0x02]
+    );
+    assert_eq!(
+        encode(&TestEvent::Release {
+            duration: TestDuration(80),
+            click_follows: false,
+        }),
+        [1, 80, 0]
+    );
+    assert_eq!(encode(&TestEvent::Click { count: 2 }), [2, 2]);
+    assert_eq!(
+        encode(&TestEvent::Hold {
+            clicks_before: 1,
+            level: 3,
+        }),
+        [3, 1, 3]
+    );
+    assert_eq!(
+        encode(&TestEvent::HoldEnd {
+            clicks_before: 0,
+            levels: 2,
+            duration: TestDuration(700),
+        }),
+        [4, 0, 2, 0xBC, 0x05]
+    );
+    assert_eq!(
+        encode(&TestEvent::Stuck {
+            duration: TestDuration(5),
+        }),
+        [5, 5]
+    );
+    assert_eq!(
+        encode(&TestEvent::Glitch {
+            duration: TestDuration(3),
+        }),
+        [6, 3]
+    );
+    assert_eq!(
+        encode(&TestEvent::HoldStage {
+            index: 1,
+            clicks_before: 0,
+        }),
+        [7, 1, 0]
+    );
+    assert_eq!(encode(&TestEvent::Confirmed), [10]);
+    assert_eq!(
+        encode(&TestEvent::ChannelFault { no: true, nc: true }),
+        [12, 1, 1]
+    );
+}
+
+#[test]
+fn service_timing_wire_format_is_stable() {
+    assert_eq!(encode(&ServiceTiming::<TestDuration>::Immediate), [0]);
+    assert_eq!(encode(&ServiceTiming::Delay(TestDuration(120))), [1, 120]);
+    assert_eq!(encode(&ServiceTiming::<TestDuration>::Idle), [2]);
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use butt_head::ServiceTiming;
+use common::{TestDuration, TestInstant, new_button};
+
+// --- deadline ---
+
+#[test]
+fn immediate_deadline_is_now() {
+    let timing: ServiceTiming<TestDuration> = ServiceTiming::Immediate;
+    assert_eq!(
+        timing.deadline(TestInstant::ms(42)),
+        Some(TestInstant::ms(42))
+    );
+}
+
+#[test]
+fn delay_deadline_is_now_plus_delay() {
+    let timing = ServiceTiming::Delay(TestDuration(300));
+    assert_eq!(
+        timing.deadline(TestInstant::ms(100)),
+        Some(TestInstant::ms(400))
+    );
+}
+
+#[test]
+fn idle_has_no_deadline() {
+    let timing: ServiceTiming<TestDuration> = ServiceTiming::Idle;
+    assert_eq!(timing.deadline(TestInstant::ms(100)), None);
+}
+
+#[test]
+fn unrepresentable_deadline_is_none() {
+    let timing = ServiceTiming::Delay(TestDuration(1));
+    assert_eq!(timing.deadline(TestInstant::ms(u64::MAX)), None);
+}
+
+#[test]
+fn deadline_is_absolute_across_updates() {
+    let mut button = new_button();
+    let now = TestInstant::ms(1000);
+    let result = button.update(true, now);
+
+    // Hold is due at press + hold_delay, regardless of when the hint is read.
+    assert_eq!(
+        result.next_service.deadline(now),
+        Some(TestInstant::ms(1500))
+    );
+
+    let now = TestInstant::ms(1200);
+    let result = button.update(true, now);
+    assert_eq!(
+        result.next_service.deadline(now),
+        Some(TestInstant::ms(1500))
+    );
+}
@@ -0,0 +1,137 @@
+#![cfg(feature = "sim")]
+
+use butt_head::sim::{SimDuration, SimInstant, Timeline};
+use butt_head::{ButtHead, Config, Event};
+
+static CONFIG: Config<SimDuration> =
+    Config::new(SimDuration(300), SimDuration(500), SimDuration(200));
+
+static ACTIVE_LOW_CONFIG: Config<SimDuration> = Config {
+    active_low: true,
+    ..CONFIG
+};
+
+// --- Timeline ---
+
+#[test]
+fn double_click_resolves_at_exact_timeout() {
+    let events = Timeline::new()
+        .press(0)
+        .release(80)
+        .press(150)
+        .release(200)
+        .run(&CONFIG);
+
+    assert_eq!(
+        events,
+        [
+            (SimInstant(0), Event::Press { at: SimInstant(0) }),
+            (
+                SimInstant(80),
+                Event::Release {
+                    duration: SimDuration(80),
+                    click_follows: true
+                }
+            ),
+            (
+                SimInstant(150),
+                Event::Press {
+                    at: SimInstant(150)
+                }
+            ),
+            (
+                SimInstant(200),
+                Event::Release {
+                    duration: SimDuration(50),
+                    click_follows: true
+                }
+            ),
+            (SimInstant(500), Event::Click { count: 2 }),
+        ]
+    );
+}
+
+#[test]
+fn hold_events_fire_on_schedule() {
+    let events = Timeline::new().press(0).release(950).run(&CONFIG);
+
+    let holds: Vec<_> = events
+        .iter()
+        .filter(|(_, e)| matches!(e, Event::Hold { .. }))
+        .map(|(at, _)| *at)
+        .collect();
+
+    assert_eq!(holds, [SimInstant(500), SimInstant(700), SimInstant(900)]);
+}
+
+#[test]
+fn hold_end_follows_release_immediately() {
+    let events = Timeline::new().press(0).release(600).run(&CONFIG);
+
+    assert_eq!(
+        events.last(),
+        Some(&(
+            SimInstant(600),
+            Event::HoldEnd {
+                clicks_before: 0,
+                levels: 1,
+                duration: SimDuration(600)
+            }
+        ))
+    );
+}
+
+#[test]
+fn click_shorthand_matches_press_release() {
+    let a = Timeline::new().click(0, 80).run(&CONFIG);
+    let b = Timeline::new().press(0).release(80).run(&CONFIG);
+
+    assert_eq!(a, b);
+}
+
+#[test]
+fn until_cuts_off_an_endless_hold() {
+    let events = Timeline::new().press(0).until(1000).run(&CONFIG);
+
+    assert_eq!(
+        events.last(),
+        Some(&(
+            SimInstant(900),
+            Event::Hold {
+                clicks_before: 0,
+                level: 2
+            }
+        ))
+    );
+}
+
+#[test]
+fn endless_hold_without_until_stops_after_default_tail() {
+    let events = Timeline::new().press(0).run(&CONFIG);
+
+    let (last, _) = events.last().unwrap();
+    assert!(last.0 <= butt_head::sim::DEFAULT_TAIL_MS);
+}
+
+#[test]
+fn empty_timeline_produces_no_events() {
+    assert!(Timeline::new().run(&CONFIG).is_empty());
+}
+
+#[test]
+fn timeline_levels_are_logical_for_active_low() {
+    let events = Timeline::new().click(0, 80).run(&ACTIVE_LOW_CONFIG);
+
+    assert_eq!(
+        events.last(),
+        Some(&(SimInstant(380), Event::Click { count: 1 }))
+    );
+}
+
+#[test]
+fn run_on_drives_existing_button() {
+    let mut button = ButtHead::new(&CONFIG);
+    Timeline::new().press(0).until(100).run_on(&mut button);
+
+    assert!(button.is_pressed());
+}
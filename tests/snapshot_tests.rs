@@ -0,0 +1,345 @@
+mod common;
+
+use butt_head::{ButtHead, ButtonState, Event, RestoreError, SNAPSHOT_VERSION, TimeDuration};
+use common::{CONFIG, TestDuration, TestInstant, new_button};
+
+// --- Round trip ---
+
+#[test]
+fn idle_round_trips() {
+    let button = new_button();
+    let snapshot = button.snapshot(TestInstant::ms(1000));
+
+    let restored = ButtHead::restore(
+        &CONFIG,
+        &snapshot,
+        TestInstant::ms(5000),
+        TestDuration::ZERO,
+    )
+    .unwrap();
+
+    assert_eq!(restored.state(), ButtonState::Idle);
+    assert!(!restored.is_pressed());
+}
+
+#[test]
+fn wait_for_multi_click_round_trips_with_elapsed_time() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(1000));
+    button.update(false, TestInstant::ms(1100));
+    let snapshot = button.snapshot(TestInstant::ms(1150));
+
+    // Woke up 100ms later on a clock that restarted.
+    let restored =
+        ButtHead::restore(&CONFIG, &snapshot, TestInstant::ms(5000), TestDuration(100)).unwrap();
+
+    assert_eq!(
+        restored.state(),
+        ButtonState::WaitForMultiClick {
+            clicks: 1,
+            since: TestInstant::ms(4850)
+        }
+    );
+}
+
+#[test]
+fn restore_after_very_long_sleep_clamps_instant() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(1000));
+    button.update(false, TestInstant::ms(1100));
+    let snapshot = button.snapshot(TestInstant::ms(1150));
+
+    let restored = ButtHead::restore(
+        &CONFIG,
+        &snapshot,
+        TestInstant::ms(5000),
+        TestDuration(u64::MAX),
+    )
+    .unwrap();
+
+    assert_eq!(
+        restored.state(),
+        ButtonState::WaitForMultiClick {
+            clicks: 1,
+            since: TestInstant::ms(5000)
+        }
+    );
+}
+
+#[test]
+fn pressed_round_trips_with_hold_progress() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(1000));
+    button.update(true, TestInstant::ms(1500)); // Hold level 0
+    let snapshot = button.snapshot(TestInstant::ms(1600));
+
+    let mut restored = ButtHead::restore(
+        &CONFIG,
+        &snapshot,
+        TestInstant::ms(1600),
+        TestDuration::ZERO,
+    )
+    .unwrap();
+
+    assert!(restored.is_pressed());
+    assert_eq!(restored.press_instant(), Some(TestInstant::ms(1000)));
+    let result = restored.update(true, TestInstant::ms(1700));
+    assert_eq!(
+        result.event,
+        Some(Event::Hold {
+            clicks_before: 0,
+            level: 1
+        })
+    );
+}
+
+#[test]
+fn double_click_spanning_sleep_resolves_to_count_2() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(1000));
+    button.update(false, TestInstant::ms(1080));
+    let snapshot = button.snapshot(TestInstant::ms(1080));
+
+    // Deep sleep for 70ms, RAM lost; second press wakes the device.
+    let mut button =
+        ButtHead::restore(&CONFIG, &snapshot, TestInstant::ms(5000), TestDuration(70)).unwrap();
+    button.update(true, TestInstant::ms(5000));
+    button.update(false, TestInstant::ms(5050));
+    let result = button.update(false, TestInstant::ms(5350));
+
+    assert_eq!(result.event, Some(Event::Click { count: 2 }));
+}
+
+#[test]
+fn restored_instant_is_clamped_to_now_when_clock_underflows() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(1000));
+    button.update(false, TestInstant::ms(1100));
+    let snapshot = button.snapshot(TestInstant::ms(1200));
+
+    let restored =
+        ButtHead::restore(&CONFIG, &snapshot, TestInstant::ms(50), TestDuration::ZERO).unwrap();
+
+    assert_eq!(
+        restored.state(),
+        ButtonState::WaitForMultiClick {
+            clicks: 1,
+            since: TestInstant::ms(50)
+        }
+    );
+}
+
+#[test]
+fn suppressed_round_trips() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(1000));
+    button.suppress_until_release();
+    let snapshot = button.snapshot(TestInstant::ms(1200));
+
+    let mut restored = ButtHead::restore(
+        &CONFIG,
+        &snapshot,
+        TestInstant::ms(1200),
+        TestDuration::ZERO,
+    )
+    .unwrap();
+
+    assert_eq!(
+        restored.state(),
+        ButtonState::Suppressed {
+            since: TestInstant::ms(1000)
+        }
+    );
+    assert_eq!(restored.update(false, TestInstant::ms(1300)).event, None);
+}
+
+// --- Validation ---
+
+#[test]
+fn snapshot_starts_with_version_byte() {
+    let button = new_button();
+    assert_eq!(button.snapshot(TestInstant::ms(0))[0], SNAPSHOT_VERSION);
+}
+
+#[test]
+fn restore_rejects_unknown_version() {
+    let mut snapshot = new_button().snapshot(TestInstant::ms(0));
+    snapshot[0] = 0xFF;
+
+    let result = ButtHead::restore(&CONFIG, &snapshot, TestInstant::ms(0), TestDuration::ZERO);
+
+    assert!(matches!(
+        result,
+        Err(RestoreError::UnsupportedVersion(0xFF))
+    ));
+}
+
+#[test]
+fn restore_rejects_cleared_storage() {
+    let snapshot = [0u8; butt_head::SNAPSHOT_LEN];
+
+    let result = ButtHead::restore(&CONFIG, &snapshot, TestInstant::ms(0), TestDuration::ZERO);
+
+    assert!(matches!(result, Err(RestoreError::UnsupportedVersion(0))));
+}
+
+#[test]
+fn restore_rejects_corrupted_payload() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    let mut snapshot = button.snapshot(TestInstant::ms(100));
+    snapshot[9] ^= 0x01;
+
+    let result = ButtHead::restore(&CONFIG, &snapshot, TestInstant::ms(100), TestDuration::ZERO);
+
+    assert!(matches!(result, Err(RestoreError::ChecksumMismatch)));
+}
+
+#[test]
+fn disabled_round_trips() {
+    let mut button = new_button();
+    button.disable();
+    let snapshot = button.snapshot(TestInstant::ms(1000));
+
+    let mut restored = ButtHead::restore(
+        &CONFIG,
+        &snapshot,
+        TestInstant::ms(1000),
+        TestDuration::ZERO,
+    )
+    .unwrap();
+
+    assert!(!restored.is_enabled());
+    assert_eq!(restored.update(true, TestInstant::ms(1100)).event, None);
+}
+
+#[test]
+fn disabled_while_held_round_trips() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.disable();
+    let snapshot = button.snapshot(TestInstant::ms(1000));
+
+    let restored = ButtHead::restore(
+        &CONFIG,
+        &snapshot,
+        TestInstant::ms(1000),
+        TestDuration::ZERO,
+    )
+    .unwrap();
+
+    assert!(!restored.is_enabled());
+    assert!(restored.is_pressed());
+    assert_eq!(restored.state(), ButtonState::Idle);
+}
+
+#[test]
+fn snapshot_after_reset_during_press_restores() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.reset();
+    let snapshot = button.snapshot(TestInstant::ms(100));
+
+    let restored = ButtHead::restore(&CONFIG, &snapshot, TestInstant::ms(100), TestDuration::ZERO);
+
+    assert!(restored.is_ok());
+}
+
+#[test]
+fn pending_press_round_trips_with_previous_release() {
+    static MIN_PRESS: butt_head::Config<TestDuration> = butt_head::Config {
+        min_press_duration: Some(TestDuration(5)),
+        report_glitches: true,
+        ..CONFIG
+    };
+    let mut button = ButtHead::new(&MIN_PRESS);
+    button.update(true, TestInstant::ms(0));
+    button.update(true, TestInstant::ms(5));
+    button.update(false, TestInstant::ms(100));
+    button.update(true, TestInstant::ms(200));
+    let snapshot = button.snapshot(TestInstant::ms(201));
+
+    let mut restored = ButtHead::restore(
+        &MIN_PRESS,
+        &snapshot,
+        TestInstant::ms(201),
+        TestDuration::ZERO,
+    )
+    .unwrap();
+
+    assert!(matches!(
+        restored.update(false, TestInstant::ms(202)).event,
+        Some(Event::Glitch { .. })
+    ));
+    assert_eq!(
+        restored.state(),
+        ButtonState::WaitForMultiClick {
+            clicks: 1,
+            since: TestInstant::ms(100)
+        }
+    );
+}
+
+#[test]
+fn confirming_round_trips_with_progress_schedule() {
+    static PROGRESS: butt_head::Config<TestDuration> = butt_head::Config {
+        confirm_duration: Some(TestDuration(3000)),
+        confirm_progress_interval: Some(TestDuration(1000)),
+        ..CONFIG
+    };
+    let mut button = ButtHead::new(&PROGRESS);
+    button.update(true, TestInstant::ms(0));
+    button.update(true, TestInstant::ms(1000));
+    let snapshot = button.snapshot(TestInstant::ms(1500));
+
+    let mut restored = ButtHead::restore(
+        &PROGRESS,
+        &snapshot,
+        TestInstant::ms(1500),
+        TestDuration::ZERO,
+    )
+    .unwrap();
+
+    assert_eq!(
+        restored.update(true, TestInstant::ms(2000)).event,
+        Some(Event::ConfirmProgress {
+            elapsed: TestDuration(2000)
+        })
+    );
+    assert_eq!(
+        restored.update(true, TestInstant::ms(3000)).event,
+        Some(Event::Confirmed)
+    );
+}
+
+#[test]
+fn cooldown_round_trips_with_ignored_press() {
+    static COOLDOWN: butt_head::Config<TestDuration> = butt_head::Config {
+        confirm_duration: Some(TestDuration(3000)),
+        confirm_cooldown: Some(TestDuration(2000)),
+        ..CONFIG
+    };
+    let mut button = ButtHead::new(&COOLDOWN);
+    button.update(true, TestInstant::ms(0));
+    button.update(true, TestInstant::ms(3000));
+    button.update(false, TestInstant::ms(3500));
+    button.update(true, TestInstant::ms(4000));
+    let snapshot = button.snapshot(TestInstant::ms(4100));
+
+    let mut restored = ButtHead::restore(
+        &COOLDOWN,
+        &snapshot,
+        TestInstant::ms(4100),
+        TestDuration::ZERO,
+    )
+    .unwrap();
+
+    assert_eq!(restored.press_instant(), Some(TestInstant::ms(4000)));
+    restored.update(true, TestInstant::ms(5500));
+    assert_eq!(
+        restored.state(),
+        ButtonState::Suppressed {
+            since: TestInstant::ms(4000)
+        }
+    );
+}
@@ -1,5 +1,6 @@
 mod common;
 
+use butt_head::ButtonState;
 use common::{TestDuration, TestInstant, new_button};
 
 // --- is_pressed ---
@@ -73,3 +74,124 @@ fn press_instant_none_after_release() {
     button.update(false, TestInstant::ms(100));
     assert_eq!(button.press_instant(), None);
 }
+
+// --- state ---
+
+#[test]
+fn state_idle_initially() {
+    let button = new_button();
+    assert_eq!(button.state(), ButtonState::Idle);
+}
+
+#[test]
+fn state_pressed_after_press() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(10));
+    assert_eq!(
+        button.state(),
+        ButtonState::Pressed {
+            since: TestInstant::ms(10),
+            clicks: 0,
+            hold_level: 0
+        }
+    );
+}
+
+#[test]
+fn state_pressed_tracks_hold_level() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.update(true, TestInstant::ms(500)); // Hold level 0
+    assert_eq!(
+        button.state(),
+        ButtonState::Pressed {
+            since: TestInstant::ms(0),
+            clicks: 0,
+            hold_level: 1
+        }
+    );
+}
+
+#[test]
+fn state_wait_for_multi_click_after_release() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.update(false, TestInstant::ms(100));
+    assert_eq!(
+        button.state(),
+        ButtonState::WaitForMultiClick {
+            clicks: 1,
+            since: TestInstant::ms(100)
+        }
+    );
+}
+
+#[test]
+fn state_idle_after_click() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.update(false, TestInstant::ms(100));
+    button.update(false, TestInstant::ms(400)); // Click
+    assert_eq!(button.state(), ButtonState::Idle);
+}
+
+// --- pending_click_count ---
+
+#[test]
+fn pending_click_count_zero_initially() {
+    let button = new_button();
+    assert_eq!(button.pending_click_count(), 0);
+}
+
+#[test]
+fn pending_click_count_counts_clicks_awaiting_report() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.update(false, TestInstant::ms(100));
+    assert_eq!(button.pending_click_count(), 1);
+
+    button.update(true, TestInstant::ms(200));
+    assert_eq!(button.pending_click_count(), 1);
+
+    button.update(false, TestInstant::ms(300));
+    assert_eq!(button.pending_click_count(), 2);
+}
+
+#[test]
+fn pending_click_count_zero_once_hold_reported() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.update(false, TestInstant::ms(100));
+    button.update(true, TestInstant::ms(200));
+    button.update(true, TestInstant::ms(700)); // Hold, clicks_before = 1
+    assert_eq!(button.pending_click_count(), 0);
+}
+
+// --- time_until_next_event ---
+
+#[test]
+fn time_until_next_event_none_when_idle() {
+    let button = new_button();
+    assert_eq!(button.time_until_next_event(TestInstant::ms(0)), None);
+}
+
+#[test]
+fn time_until_next_event_counts_down_to_hold() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    assert_eq!(
+        button.time_until_next_event(TestInstant::ms(200)),
+        Some(TestDuration(300))
+    );
+}
+
+#[test]
+fn time_until_next_event_counts_down_to_click() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+    button.update(false, TestInstant::ms(100));
+    assert_eq!(
+        button.time_until_next_event(TestInstant::ms(150)),
+        Some(TestDuration(250))
+    );
+}
@@ -0,0 +1,113 @@
+mod common;
+
+use butt_head::{ButtHead, Config, Event, ServiceTiming};
+use common::{CONFIG, TestDuration, TestInstant, new_button};
+
+static STUCK_CONFIG: Config<TestDuration> = Config {
+    max_press_duration: Some(TestDuration(1000)),
+    ..CONFIG
+};
+
+// --- Stuck detection ---
+
+#[test]
+fn stuck_fires_at_max_press_duration() {
+    let mut button = ButtHead::new(&STUCK_CONFIG);
+    button.update(true, TestInstant::ms(0));
+
+    let result = button.update(true, TestInstant::ms(1000));
+
+    assert_eq!(
+        result.event,
+        Some(Event::Stuck {
+            duration: TestDuration(1000)
+        })
+    );
+    assert_eq!(result.next_service, ServiceTiming::Idle);
+    assert!(button.is_stuck());
+}
+
+#[test]
+fn stuck_not_fired_before_max_press_duration() {
+    let mut button = ButtHead::new(&STUCK_CONFIG);
+    button.update(true, TestInstant::ms(0));
+
+    let result = button.update(true, TestInstant::ms(999));
+
+    assert_ne!(
+        result.event,
+        Some(Event::Stuck {
+            duration: TestDuration(999)
+        })
+    );
+    assert!(!button.is_stuck());
+}
+
+#[test]
+fn stuck_button_emits_no_further_events() {
+    let mut button = ButtHead::new(&STUCK_CONFIG);
+    button.update(true, TestInstant::ms(0));
+    button.update(true, TestInstant::ms(1000)); // Stuck
+
+    let result = button.update(true, TestInstant::ms(5000));
+    assert_eq!(result.event, None);
+
+    // The real release is swallowed as well.
+    let result = button.update(false, TestInstant::ms(6000));
+    assert_eq!(result.event, None);
+    assert_eq!(result.next_service, ServiceTiming::Idle);
+    assert!(!button.is_stuck());
+}
+
+#[test]
+fn stuck_button_recovers_after_release() {
+    let mut button = ButtHead::new(&STUCK_CONFIG);
+    button.update(true, TestInstant::ms(0));
+    button.update(true, TestInstant::ms(1000)); // Stuck
+    button.update(false, TestInstant::ms(2000)); // Quarantine lifted
+
+    let result = button.update(true, TestInstant::ms(3000));
+
+    assert_eq!(
+        result.event,
+        Some(Event::Press {
+            at: TestInstant::ms(3000)
+        })
+    );
+}
+
+#[test]
+fn stuck_button_is_still_reported_as_pressed() {
+    let mut button = ButtHead::new(&STUCK_CONFIG);
+    button.update(true, TestInstant::ms(0));
+    button.update(true, TestInstant::ms(1000)); // Stuck
+
+    assert!(button.is_pressed());
+    assert_eq!(button.press_instant(), Some(TestInstant::ms(0)));
+}
+
+#[test]
+fn stuck_never_fires_without_max_press_duration() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(0));
+
+    let result = button.update(true, TestInstant::ms(100_000));
+
+    assert!(matches!(result.event, Some(Event::Hold { .. })));
+    assert!(!button.is_stuck());
+}
+
+// --- Service timing ---
+
+#[test]
+fn service_timing_schedules_stuck_deadline_between_holds() {
+    let mut button = ButtHead::new(&STUCK_CONFIG);
+    button.update(true, TestInstant::ms(0));
+    button.update(true, TestInstant::ms(500)); // Hold level 0
+    button.update(true, TestInstant::ms(700)); // Hold level 1
+
+    // Next hold would be at t=1100, but the stuck deadline is at t=1000.
+    let result = button.update(true, TestInstant::ms(900)); // Hold level 2
+
+    assert_eq!(result.next_service, ServiceTiming::Delay(TestDuration(100)));
+}
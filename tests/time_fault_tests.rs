@@ -0,0 +1,221 @@
+mod common;
+
+use butt_head::{
+    ButtHead, ButtonState, Config, Event, ServiceTiming, TimeFaultPolicy, UpdateError,
+};
+use common::{CONFIG, TestDuration, TestInstant, new_button};
+
+static RESET_CONFIG: Config<TestDuration> = Config {
+    time_fault_policy: TimeFaultPolicy::Reset,
+    ..CONFIG
+};
+
+static ERROR_CONFIG: Config<TestDuration> = Config {
+    time_fault_policy: TimeFaultPolicy::Error,
+    ..CONFIG
+};
+
+/// Close enough to the end of the clock that `hold_delay` (500ms) overflows.
+const NEAR_END: u64 = u64::MAX - 100;
+
+// --- Time going backwards ---
+
+#[test]
+fn clamp_treats_rewound_time_as_previous_now() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(100));
+
+    let result = button.update(true, TestInstant::ms(50));
+    assert_eq!(result.event, None);
+    assert_eq!(result.next_service, ServiceTiming::Delay(TestDuration(500)));
+
+    // The hold is still due 500ms after the real press.
+    let result = button.update(true, TestInstant::ms(600));
+    assert_eq!(
+        result.event,
+        Some(Event::Hold {
+            clicks_before: 0,
+            level: 0
+        })
+    );
+}
+
+#[test]
+fn clamp_dates_edge_at_previous_now() {
+    let mut button = new_button();
+    button.update(true, TestInstant::ms(100));
+    button.update(true, TestInstant::ms(200));
+
+    let result = button.update(false, TestInstant::ms(150));
+
+    assert_eq!(
+        result.event,
+        Some(Event::Release {
+            duration: TestDuration(100),
+            click_follows: true,
+        })
+    );
+}
+
+#[test]
+fn error_policy_reports_time_went_backwards() {
+    let mut button = ButtHead::new(&ERROR_CONFIG);
+    button.try_update(true, TestInstant::ms(100)).unwrap();
+
+    let result = button.try_update(false, TestInstant::ms(50));
+
+    assert_eq!(result.unwrap_err(), UpdateError::TimeWentBackwards);
+}
+
+#[test]
+fn error_leaves_input_unconsumed_for_retry() {
+    let mut button = ButtHead::new(&ERROR_CONFIG);
+    button.try_update(true, TestInstant::ms(100)).unwrap();
+    let _ = button.try_update(false, TestInstant::ms(50));
+
+    assert!(button.is_pressed());
+    let result = button.try_update(false, TestInstant::ms(180)).unwrap();
+    assert_eq!(
+        result.event,
+        Some(Event::Release {
+            duration: TestDuration(80),
+            click_follows: true,
+        })
+    );
+}
+
+#[test]
+fn update_falls_back_to_clamp_under_error_policy() {
+    let mut button = ButtHead::new(&ERROR_CONFIG);
+    button.update(true, TestInstant::ms(100));
+
+    let result = button.update(true, TestInstant::ms(50));
+
+    assert_eq!(result.event, None);
+    assert_eq!(result.next_service, ServiceTiming::Delay(TestDuration(500)));
+}
+
+#[test]
+fn reset_abandons_gesture_silently() {
+    let mut button = ButtHead::new(&RESET_CONFIG);
+    button.update(true, TestInstant::ms(100));
+    button.update(false, TestInstant::ms(200)); // Click pending
+
+    let result = button.update(false, TestInstant::ms(150));
+    assert_eq!(result.event, None);
+    assert_eq!(result.next_service, ServiceTiming::Idle);
+
+    // The pending click never fires.
+    let result = button.update(false, TestInstant::ms(1_000));
+    assert_eq!(result.event, None);
+}
+
+#[test]
+fn reset_ignores_press_in_progress_until_released() {
+    let mut button = ButtHead::new(&RESET_CONFIG);
+    button.update(true, TestInstant::ms(100));
+    button.update(true, TestInstant::ms(50)); // Reset
+
+    assert_eq!(button.update(true, TestInstant::ms(600)).event, None);
+    assert_eq!(button.update(false, TestInstant::ms(700)).event, None);
+
+    let result = button.update(true, TestInstant::ms(800));
+    assert_eq!(
+        result.event,
+        Some(Event::Press {
+            at: TestInstant::ms(800)
+        })
+    );
+}
+
+#[test]
+fn equal_timestamps_are_not_a_fault() {
+    let mut button = ButtHead::new(&ERROR_CONFIG);
+    button.try_update(true, TestInstant::ms(100)).unwrap();
+
+    assert!(button.try_update(false, TestInstant::ms(100)).is_ok());
+}
+
+// --- Deadline overflow ---
+
+#[test]
+fn clamp_schedules_no_hold_past_end_of_clock() {
+    let mut button = new_button();
+
+    let result = button.update(true, TestInstant(NEAR_END));
+    assert_eq!(
+        result.event,
+        Some(Event::Press {
+            at: TestInstant(NEAR_END)
+        })
+    );
+    assert_eq!(result.next_service, ServiceTiming::Idle);
+    assert_eq!(button.time_until_next_event(TestInstant(NEAR_END)), None);
+
+    // Previously the deadline collapsed to `now` and Hold fired instantly.
+    let result = button.update(true, TestInstant(NEAR_END + 1));
+    assert_eq!(result.event, None);
+
+    let result = button.update(false, TestInstant(NEAR_END + 50));
+    assert_eq!(
+        result.event,
+        Some(Event::Release {
+            duration: TestDuration(50),
+            click_follows: true,
+        })
+    );
+}
+
+#[test]
+fn clamp_stops_hold_repeats_past_end_of_clock() {
+    let mut button = new_button();
+    button.update(true, TestInstant(NEAR_END - 500));
+
+    let result = button.update(true, TestInstant(NEAR_END));
+    assert_eq!(
+        result.event,
+        Some(Event::Hold {
+            clicks_before: 0,
+            level: 0
+        })
+    );
+    assert_eq!(result.next_service, ServiceTiming::Idle);
+}
+
+#[test]
+fn error_policy_reports_deadline_overflow() {
+    let mut button = ButtHead::new(&ERROR_CONFIG);
+
+    let result = button.try_update(true, TestInstant(NEAR_END));
+
+    assert_eq!(result.unwrap_err(), UpdateError::DeadlineOverflow);
+    assert!(!button.is_pressed());
+    assert_eq!(button.state(), ButtonState::Idle);
+}
+
+#[test]
+fn error_policy_reports_hold_interval_overflow_without_emitting_hold() {
+    let mut button = ButtHead::new(&ERROR_CONFIG);
+    button
+        .try_update(true, TestInstant(NEAR_END - 500))
+        .unwrap();
+
+    let result = button.try_update(true, TestInstant(NEAR_END));
+
+    assert_eq!(result.unwrap_err(), UpdateError::DeadlineOverflow);
+    assert!(matches!(
+        button.state(),
+        ButtonState::Pressed { hold_level: 0, .. }
+    ));
+}
+
+#[test]
+fn reset_policy_drops_press_whose_deadline_overflows() {
+    let mut button = ButtHead::new(&RESET_CONFIG);
+
+    let result = button.update(true, TestInstant(NEAR_END));
+    assert_eq!(result.event, None);
+    assert_eq!(result.next_service, ServiceTiming::Idle);
+
+    assert_eq!(button.update(false, TestInstant(NEAR_END + 10)).event, None);
+}
@@ -0,0 +1,215 @@
+mod common;
+
+use butt_head::trace::{self, MAX_DELTA_MS, Recorder, TraceSample, replay};
+use butt_head::{ButtHead, Config, Event};
+use common::{CONFIG, TestDuration, TestInstant};
+
+static SLOW_CONFIG: Config<TestDuration> = Config {
+    click_timeout: TestDuration(500),
+    hold_delay: TestDuration(1000),
+    ..CONFIG
+};
+
+fn sample(delta_ms: u16, level: bool) -> TraceSample {
+    TraceSample { delta_ms, level }
+}
+
+fn replay_all(
+    config: &'static Config<TestDuration>,
+    samples: &[TraceSample],
+) -> Vec<(TestInstant, Event<TestDuration, TestInstant>)> {
+    let mut events = Vec::new();
+    replay(
+        config,
+        samples.iter().copied(),
+        TestInstant::ms(1000),
+        TestDuration(5000),
+        |at, event| events.push((at, event)),
+    );
+    events
+}
+
+// --- Encoding ---
+
+#[test]
+fn sample_round_trips_through_bytes() {
+    for s in [
+        sample(0, false),
+        sample(1234, true),
+        sample(MAX_DELTA_MS, true),
+    ] {
+        assert_eq!(TraceSample::from_bytes(s.to_bytes()), s);
+    }
+}
+
+#[test]
+fn decode_reads_concatenated_samples() {
+    let bytes = [sample(10, true).to_bytes(), sample(80, false).to_bytes()].concat();
+
+    let samples: Vec<_> = trace::decode(&bytes).collect();
+
+    assert_eq!(samples, [sample(10, true), sample(80, false)]);
+}
+
+// --- Recorder ---
+
+#[test]
+fn recorder_records_only_level_changes() {
+    let mut recorder: Recorder<TestInstant, 8> = Recorder::new(&CONFIG);
+    recorder.update(false, TestInstant::ms(0));
+    recorder.update(true, TestInstant::ms(100));
+    recorder.update(true, TestInstant::ms(150));
+    recorder.update(false, TestInstant::ms(180));
+
+    let samples: Vec<_> = recorder.samples().collect();
+
+    assert_eq!(
+        samples,
+        [sample(0, false), sample(100, true), sample(80, false)]
+    );
+}
+
+#[test]
+fn recorder_forwards_to_button() {
+    let mut recorder: Recorder<TestInstant, 8> = Recorder::new(&CONFIG);
+
+    let result = recorder.update(true, TestInstant::ms(0));
+
+    assert_eq!(
+        result.event,
+        Some(Event::Press {
+            at: TestInstant::ms(0)
+        })
+    );
+    assert!(recorder.button().is_pressed());
+}
+
+#[test]
+fn recorder_wraps_existing_button() {
+    let button = ButtHead::resume_from_wake(&CONFIG, TestInstant::ms(0));
+    let mut recorder: Recorder<TestInstant, 8> = Recorder::wrap(button);
+
+    // The press that woke the MCU is dated to the wake edge.
+    let result = recorder.update(false, TestInstant::ms(40));
+    assert_eq!(
+        result.event,
+        Some(Event::Press {
+            at: TestInstant::ms(0)
+        })
+    );
+    assert_eq!(recorder.samples().collect::<Vec<_>>(), [sample(0, false)]);
+}
+
+#[test]
+fn recorder_gives_access_to_gesture_control() {
+    let mut recorder: Recorder<TestInstant, 8> = Recorder::new(&CONFIG);
+    recorder.try_update(true, TestInstant::ms(0)).unwrap();
+
+    assert!(recorder.button_mut().cancel_gesture());
+    let result = recorder.try_update(false, TestInstant::ms(100)).unwrap();
+
+    assert_eq!(result.event, None);
+    assert_eq!(
+        recorder.samples().collect::<Vec<_>>(),
+        [sample(0, true), sample(100, false)]
+    );
+}
+
+#[test]
+fn recorder_splits_long_gaps() {
+    let mut recorder: Recorder<TestInstant, 8> = Recorder::new(&CONFIG);
+    recorder.update(false, TestInstant::ms(0));
+    recorder.update(true, TestInstant::ms(MAX_DELTA_MS as u64 + 10));
+
+    let samples: Vec<_> = recorder.samples().collect();
+
+    assert_eq!(
+        samples,
+        [
+            sample(0, false),
+            sample(MAX_DELTA_MS, false),
+            sample(10, true)
+        ]
+    );
+}
+
+#[test]
+fn recorder_ring_buffer_keeps_newest_samples() {
+    let mut recorder: Recorder<TestInstant, 2> = Recorder::new(&CONFIG);
+    recorder.update(true, TestInstant::ms(0));
+    recorder.update(false, TestInstant::ms(10));
+    recorder.update(true, TestInstant::ms(30));
+
+    let samples: Vec<_> = recorder.samples().collect();
+
+    assert_eq!(recorder.len(), 2);
+    assert_eq!(samples, [sample(10, false), sample(20, true)]);
+}
+
+#[test]
+fn recorder_write_bytes_matches_samples() {
+    let mut recorder: Recorder<TestInstant, 4> = Recorder::new(&CONFIG);
+    recorder.update(true, TestInstant::ms(0));
+    recorder.update(false, TestInstant::ms(50));
+    let mut buffer = [0u8; 16];
+
+    let written = recorder.write_bytes(&mut buffer);
+
+    assert_eq!(written, 4);
+    let decoded: Vec<_> = trace::decode(&buffer[..written]).collect();
+    assert_eq!(decoded, recorder.samples().collect::<Vec<_>>());
+}
+
+#[test]
+fn recorder_clear_empties_buffer() {
+    let mut recorder: Recorder<TestInstant, 4> = Recorder::new(&CONFIG);
+    recorder.update(true, TestInstant::ms(0));
+    recorder.clear();
+
+    assert!(recorder.is_empty());
+}
+
+// --- Replay ---
+
+#[test]
+fn replay_reproduces_recorded_events() {
+    let mut recorder: Recorder<TestInstant, 16> = Recorder::new(&CONFIG);
+    let mut recorded = Vec::new();
+    for t in 0..1000 {
+        let level = (100..180).contains(&t) || (250..300).contains(&t);
+        let now = TestInstant::ms(1000 + t);
+        if let Some(event) = recorder.update(level, now).event {
+            recorded.push((now, event));
+        }
+    }
+
+    let samples: Vec<_> = recorder.samples().collect();
+    let replayed = replay_all(&CONFIG, &samples);
+
+    assert_eq!(replayed, recorded);
+    assert_eq!(replayed.last().unwrap().1, Event::Click { count: 2 });
+}
+
+#[test]
+fn replay_through_different_config_changes_outcome() {
+    // Press for 600ms: a hold with CONFIG, a click with SLOW_CONFIG.
+    let samples = [sample(0, true), sample(600, false)];
+
+    let fast = replay_all(&CONFIG, &samples);
+    let slow = replay_all(&SLOW_CONFIG, &samples);
+
+    assert!(fast.iter().any(|(_, e)| matches!(e, Event::Hold { .. })));
+    assert_eq!(
+        slow.last(),
+        Some(&(TestInstant::ms(2100), Event::Click { count: 1 }))
+    );
+}
+
+#[test]
+fn replay_stops_after_tail() {
+    // Pressed forever: holds stop after the 5s tail.
+    let events = replay_all(&CONFIG, &[sample(0, true)]);
+
+    let (last, _) = events.last().unwrap();
+    assert!(last.0 <= 1000 + 5000);
+}
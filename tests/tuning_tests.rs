@@ -0,0 +1,242 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use butt_head::capture::LevelChange;
+use butt_head::tuning::{self, Gesture, Label, Press, Stats};
+use common::CONFIG;
+
+fn press(start_ms: u64, end_ms: u64) -> Press {
+    Press { start_ms, end_ms }
+}
+
+fn gesture(label: Label, presses: &[(u64, u64)]) -> Gesture {
+    Gesture {
+        presses: presses.iter().map(|&(s, e)| press(s, e)).collect(),
+        label,
+    }
+}
+
+/// Single clicks, double-clicks with gaps up to 180ms, and holds of at least
+/// 600ms, separated by at least a second.
+fn labelled() -> Vec<Gesture> {
+    vec![
+        gesture(Label::Click, &[(0, 90)]),
+        gesture(Label::Click, &[(1_500, 1_580), (1_700, 1_790)]),
+        gesture(Label::Hold, &[(3_000, 3_700)]),
+        gesture(Label::Click, &[(5_000, 5_120), (5_300, 5_400)]),
+        gesture(Label::Hold, &[(6_500, 6_580), (6_700, 7_300)]),
+        gesture(Label::Click, &[(8_500, 8_600)]),
+    ]
+}
+
+// --- Distributions ---
+
+#[test]
+fn stats_of_empty_slice_are_zero() {
+    assert_eq!(Stats::of(&[]), Stats::default());
+}
+
+#[test]
+fn stats_report_min_median_p90_max() {
+    let values: Vec<u64> = (1..=11).map(|v| v * 10).collect();
+
+    let stats = Stats::of(&values);
+
+    assert_eq!(stats.count, 11);
+    assert_eq!(stats.min, 10);
+    assert_eq!(stats.median, 60);
+    assert_eq!(stats.p90, 100);
+    assert_eq!(stats.max, 110);
+}
+
+#[test]
+fn analyze_measures_durations_and_gaps() {
+    let presses = [press(0, 100), press(150, 200), press(1_200, 1_900)];
+
+    let analysis = tuning::analyze(&presses);
+
+    assert_eq!(analysis.press_durations.min, 50);
+    assert_eq!(analysis.press_durations.max, 700);
+    assert_eq!(analysis.gaps.count, 2);
+    assert_eq!(analysis.gaps.min, 50);
+    assert_eq!(analysis.gaps.max, 1_000);
+}
+
+#[test]
+fn presses_from_changes_honours_active_low() {
+    let changes = [
+        LevelChange {
+            at_ns: 0,
+            level: true,
+        },
+        LevelChange {
+            at_ns: 10_000_000,
+            level: false,
+        },
+        LevelChange {
+            at_ns: 90_000_000,
+            level: true,
+        },
+        LevelChange {
+            at_ns: 200_000_000,
+            level: false,
+        },
+    ];
+
+    let presses = tuning::presses_from_changes(&changes, true);
+
+    // The final press is never released and is dropped.
+    assert_eq!(presses, vec![press(10, 90)]);
+}
+
+// --- Evaluation ---
+
+#[test]
+fn short_timeout_splits_double_clicks() {
+    let evaluation = tuning::evaluate(&labelled(), 150, 500);
+
+    assert_eq!(evaluation.split_multi_clicks, 1); // the 180ms gap
+    assert_eq!(evaluation.merged_clicks, 0);
+}
+
+#[test]
+fn long_timeout_merges_separate_clicks() {
+    let evaluation = tuning::evaluate(&labelled(), 1_500, 500);
+
+    assert_eq!(evaluation.split_multi_clicks, 0);
+    assert_eq!(evaluation.merged_clicks, 3);
+}
+
+#[test]
+fn gap_after_hold_never_merges() {
+    let gestures = [
+        gesture(Label::Hold, &[(0, 800)]),
+        gesture(Label::Click, &[(850, 900)]),
+    ];
+
+    let evaluation = tuning::evaluate(&gestures, 300, 500);
+
+    assert_eq!(evaluation.merged_clicks, 0);
+}
+
+#[test]
+fn hold_delay_misclassification_in_both_directions() {
+    let evaluation = tuning::evaluate(&labelled(), 300, 110);
+    assert_eq!(evaluation.clicks_as_holds, 1); // the 120ms press
+    assert_eq!(evaluation.holds_as_clicks, 0);
+
+    let evaluation = tuning::evaluate(&labelled(), 300, 650);
+    assert_eq!(evaluation.clicks_as_holds, 0);
+    assert_eq!(evaluation.holds_as_clicks, 1); // the 600ms hold
+}
+
+#[test]
+fn evaluation_reports_latency_cost() {
+    let evaluation = tuning::evaluate(&labelled(), 250, 400);
+
+    assert_eq!(evaluation.click_latency_ms, 250);
+    assert_eq!(evaluation.hold_latency_ms, 400);
+    assert_eq!(evaluation.errors(), 0);
+}
+
+// --- Recommendation ---
+
+#[test]
+fn recommendation_classifies_labelled_data_without_errors() {
+    let recommendation = tuning::recommend(&labelled(), &CONFIG);
+
+    assert_eq!(recommendation.best.errors(), 0);
+    // Smallest error-free values (181, 121) padded by 25%.
+    assert_eq!(recommendation.best.click_timeout_ms, 226);
+    assert_eq!(recommendation.best.hold_delay_ms, 151);
+}
+
+#[test]
+fn recommendation_lists_latency_trade_offs_in_order() {
+    let recommendation = tuning::recommend(&labelled(), &CONFIG);
+
+    let options = &recommendation.click_timeout_options;
+    assert!(
+        options
+            .windows(2)
+            .all(|w| w[0].click_latency_ms < w[1].click_latency_ms)
+    );
+    assert!(options.iter().any(|o| o.split_multi_clicks > 0));
+    assert!(options.iter().any(|o| o.merged_clicks > 0));
+    assert!(!recommendation.hold_delay_options.is_empty());
+}
+
+#[test]
+fn margin_is_dropped_when_it_would_add_errors() {
+    let gestures = [
+        gesture(Label::Click, &[(0, 50), (150, 200)]),
+        gesture(Label::Click, &[(310, 360)]),
+    ];
+
+    let recommendation = tuning::recommend(&gestures, &CONFIG);
+
+    // 101ms is the only error-free timeout range start; 126 would merge.
+    assert_eq!(recommendation.best.click_timeout_ms, 101);
+    assert_eq!(recommendation.best.errors(), 0);
+}
+
+#[test]
+fn parameters_without_data_keep_current_values() {
+    let recommendation = tuning::recommend(&[gesture(Label::Click, &[(0, 90)])], &CONFIG);
+
+    // A lone single click has no gaps to learn `click_timeout` from.
+    assert_eq!(recommendation.best.click_timeout_ms, 300);
+    assert!(recommendation.click_timeout_options.is_empty());
+
+    let recommendation = tuning::recommend(&[], &CONFIG);
+    assert_eq!(recommendation.best.click_timeout_ms, 300);
+    assert_eq!(recommendation.best.hold_delay_ms, 500);
+}
+
+#[test]
+fn single_clicks_keep_current_values() {
+    let gestures: Vec<Gesture> = [0, 2_000, 5_000, 9_000]
+        .iter()
+        .map(|&start| gesture(Label::Click, &[(start, start + 80)]))
+        .collect();
+
+    let recommendation = tuning::recommend(&gestures, &CONFIG);
+
+    // No multi-click bounds click_timeout from below and no hold sets
+    // hold_delay, so both stay as configured.
+    assert_eq!(recommendation.best.click_timeout_ms, 300);
+    assert_eq!(recommendation.best.hold_delay_ms, 500);
+    assert_eq!(recommendation.best.errors(), 0);
+}
+
+// --- Unlabelled ---
+
+#[test]
+fn infer_gestures_groups_by_gap_and_duration() {
+    let presses: Vec<Press> = labelled().into_iter().flat_map(|g| g.presses).collect();
+
+    let inferred = tuning::infer_gestures(&presses);
+
+    assert_eq!(inferred, labelled());
+}
+
+#[test]
+fn unlabelled_recommendation_matches_labelled() {
+    let presses: Vec<Press> = labelled().into_iter().flat_map(|g| g.presses).collect();
+
+    assert_eq!(
+        tuning::recommend_unlabelled(&presses, &CONFIG),
+        tuning::recommend(&labelled(), &CONFIG)
+    );
+}
+
+#[test]
+fn unimodal_data_is_treated_as_single_clicks() {
+    let presses = [press(0, 100), press(1_000, 1_100), press(2_000, 2_100)];
+
+    let inferred = tuning::infer_gestures(&presses);
+
+    assert_eq!(inferred.len(), 3);
+    assert!(inferred.iter().all(|g| g.label == Label::Click));
+}
@@ -0,0 +1,324 @@
+mod common;
+
+use butt_head::{ServiceTiming, TwoHandControl, TwoHandEvent, TwoHandFault};
+use common::{CONFIG, TestDuration, TestInstant};
+
+const WINDOW: TestDuration = TestDuration(500);
+
+/// A control that has seen both buttons released at 0.
+fn new_control() -> TwoHandControl<TestInstant> {
+    let mut control = TwoHandControl::new(&CONFIG, WINDOW);
+    control.update(false, false, TestInstant::ms(0));
+    control
+}
+
+fn event(
+    control: &mut TwoHandControl<TestInstant>,
+    left: bool,
+    right: bool,
+    ms: u64,
+) -> Option<TwoHandEvent> {
+    control.update(left, right, TestInstant::ms(ms)).event
+}
+
+const ENGAGED: Option<TwoHandEvent> = Some(TwoHandEvent::Engaged);
+const DISENGAGED: Option<TwoHandEvent> = Some(TwoHandEvent::Disengaged);
+const SYNC_TIMEOUT: Option<TwoHandEvent> = Some(TwoHandEvent::Fault(TwoHandFault::SyncTimeout));
+const RELEASE_REQUIRED: Option<TwoHandEvent> =
+    Some(TwoHandEvent::Fault(TwoHandFault::ReleaseRequired));
+
+// --- Synchronous window ---
+
+#[test]
+fn simultaneous_press_engages() {
+    let mut control = new_control();
+
+    assert_eq!(event(&mut control, true, true, 100), ENGAGED);
+    assert!(control.is_engaged());
+}
+
+#[test]
+fn left_then_right_within_window_engages() {
+    let mut control = new_control();
+
+    assert_eq!(event(&mut control, true, false, 100), None);
+    assert!(!control.is_engaged());
+    assert_eq!(event(&mut control, true, true, 400), ENGAGED);
+}
+
+#[test]
+fn right_then_left_within_window_engages() {
+    let mut control = new_control();
+
+    assert_eq!(event(&mut control, false, true, 100), None);
+    assert_eq!(event(&mut control, true, true, 599), ENGAGED);
+}
+
+#[test]
+fn second_press_exactly_at_window_faults() {
+    let mut control = new_control();
+    control.update(true, false, TestInstant::ms(100));
+
+    // Same outcome whether or not the deadline was serviced first.
+    assert_eq!(event(&mut control, true, true, 600), SYNC_TIMEOUT);
+}
+
+#[test]
+fn single_press_schedules_window_deadline() {
+    let mut control = new_control();
+
+    let result = control.update(true, false, TestInstant::ms(100));
+    assert_eq!(result.next_service, ServiceTiming::Delay(WINDOW));
+
+    let result = control.update(true, false, TestInstant::ms(300));
+    assert_eq!(result.next_service, ServiceTiming::Delay(TestDuration(300)));
+}
+
+#[test]
+fn missed_window_faults_at_deadline() {
+    let mut control = new_control();
+    control.update(true, false, TestInstant::ms(100));
+
+    let result = control.update(true, false, TestInstant::ms(600));
+
+    assert_eq!(result.event, SYNC_TIMEOUT);
+    // Only the held button's own hold schedule remains.
+    assert_eq!(result.next_service, ServiceTiming::Delay(TestDuration(200)));
+}
+
+#[test]
+fn late_second_press_faults_without_deadline_service() {
+    let mut control = new_control();
+    control.update(true, false, TestInstant::ms(100));
+
+    assert_eq!(event(&mut control, true, true, 601), SYNC_TIMEOUT);
+    assert!(!control.is_engaged());
+}
+
+#[test]
+fn sync_timeout_fires_once() {
+    let mut control = new_control();
+    control.update(true, false, TestInstant::ms(100));
+    control.update(true, false, TestInstant::ms(600));
+
+    assert_eq!(event(&mut control, true, false, 2000), None);
+}
+
+#[test]
+fn releasing_single_press_within_window_restarts_cycle() {
+    let mut control = new_control();
+    control.update(true, false, TestInstant::ms(100));
+
+    let result = control.update(false, false, TestInstant::ms(300));
+    assert_eq!(result.event, None);
+    // The window is gone; only the button's click timeout remains.
+    assert_eq!(result.next_service, ServiceTiming::Delay(TestDuration(300)));
+
+    control.update(false, true, TestInstant::ms(900));
+    assert_eq!(event(&mut control, true, true, 1000), ENGAGED);
+}
+
+// --- Engaged ---
+
+#[test]
+fn output_stays_engaged_while_both_held() {
+    let mut control = new_control();
+    control.update(true, true, TestInstant::ms(100));
+
+    let result = control.update(true, true, TestInstant::ms(10_000));
+
+    assert_eq!(result.event, None);
+    assert!(control.is_engaged());
+}
+
+#[test]
+fn released_control_needs_no_service() {
+    let mut control = new_control();
+    control.update(true, true, TestInstant::ms(100));
+    control.update(false, false, TestInstant::ms(200));
+
+    // Once the buttons' click timeouts have passed, nothing is scheduled.
+    let result = control.update(false, false, TestInstant::ms(500));
+
+    assert_eq!(result.next_service, ServiceTiming::Idle);
+}
+
+#[test]
+fn releasing_left_disengages() {
+    let mut control = new_control();
+    control.update(true, true, TestInstant::ms(100));
+
+    assert_eq!(event(&mut control, false, true, 1000), DISENGAGED);
+    assert!(!control.is_engaged());
+}
+
+#[test]
+fn releasing_right_disengages() {
+    let mut control = new_control();
+    control.update(true, true, TestInstant::ms(100));
+
+    assert_eq!(event(&mut control, true, false, 1000), DISENGAGED);
+}
+
+#[test]
+fn releasing_both_disengages_once() {
+    let mut control = new_control();
+    control.update(true, true, TestInstant::ms(100));
+
+    assert_eq!(event(&mut control, false, false, 1000), DISENGAGED);
+    assert_eq!(event(&mut control, false, false, 1100), None);
+}
+
+// --- Anti-tie-down ---
+
+#[test]
+fn repress_without_releasing_both_faults() {
+    let mut control = new_control();
+    control.update(true, true, TestInstant::ms(100));
+    control.update(false, true, TestInstant::ms(1000));
+
+    assert_eq!(event(&mut control, true, true, 1100), RELEASE_REQUIRED);
+    assert!(!control.is_engaged());
+}
+
+#[test]
+fn release_required_fires_once_per_attempt() {
+    let mut control = new_control();
+    control.update(true, true, TestInstant::ms(100));
+    control.update(false, true, TestInstant::ms(1000));
+    control.update(true, true, TestInstant::ms(1100));
+
+    assert_eq!(event(&mut control, true, true, 1200), None);
+    assert_eq!(event(&mut control, false, true, 1300), None);
+    assert_eq!(event(&mut control, true, true, 1400), RELEASE_REQUIRED);
+}
+
+#[test]
+fn tied_down_button_never_engages() {
+    let mut control = new_control();
+    control.update(false, true, TestInstant::ms(100));
+    control.update(false, true, TestInstant::ms(600));
+
+    for t in (1000..5000).step_by(1000) {
+        assert_eq!(event(&mut control, true, true, t), RELEASE_REQUIRED);
+        assert_eq!(event(&mut control, false, true, t + 100), None);
+    }
+}
+
+#[test]
+fn new_cycle_after_both_released_engages() {
+    let mut control = new_control();
+    control.update(true, true, TestInstant::ms(100));
+    control.update(false, true, TestInstant::ms(1000));
+    control.update(false, false, TestInstant::ms(1100));
+
+    control.update(true, false, TestInstant::ms(2000));
+    assert_eq!(event(&mut control, true, true, 2100), ENGAGED);
+}
+
+#[test]
+fn new_cycle_after_fault_requires_release() {
+    let mut control = new_control();
+    control.update(true, false, TestInstant::ms(100));
+    control.update(true, false, TestInstant::ms(600));
+    control.update(false, false, TestInstant::ms(700));
+
+    assert_eq!(event(&mut control, true, true, 800), ENGAGED);
+}
+
+// --- Start-up ---
+
+#[test]
+fn buttons_held_at_startup_do_not_engage() {
+    let mut control = TwoHandControl::new(&CONFIG, WINDOW);
+
+    assert_eq!(event(&mut control, true, true, 0), RELEASE_REQUIRED);
+    assert_eq!(event(&mut control, true, true, 100), None);
+    assert!(!control.is_engaged());
+
+    control.update(false, false, TestInstant::ms(200));
+    assert_eq!(event(&mut control, true, true, 300), ENGAGED);
+}
+
+#[test]
+fn single_button_held_at_startup_must_be_released() {
+    let mut control = TwoHandControl::new(&CONFIG, WINDOW);
+    control.update(true, false, TestInstant::ms(0));
+
+    assert_eq!(event(&mut control, true, true, 100), RELEASE_REQUIRED);
+}
+
+// --- Buttons ---
+
+#[test]
+fn active_low_buttons_are_inverted() {
+    static ACTIVE_LOW: butt_head::Config<TestDuration> = butt_head::Config {
+        active_low: true,
+        ..CONFIG
+    };
+    let mut control = TwoHandControl::new(&ACTIVE_LOW, WINDOW);
+    control.update(true, true, TestInstant::ms(0));
+
+    assert_eq!(event(&mut control, false, false, 100), ENGAGED);
+    assert!(control.left().is_pressed());
+    assert!(control.right().is_pressed());
+}
+
+#[test]
+fn sync_window_is_reported() {
+    let control = new_control();
+
+    assert_eq!(control.sync_window(), WINDOW);
+}
+
+// --- Minimum press duration ---
+
+static MIN_PRESS: butt_head::Config<TestDuration> = butt_head::Config {
+    min_press_duration: Some(TestDuration(20)),
+    ..CONFIG
+};
+
+fn new_min_press_control() -> TwoHandControl<TestInstant> {
+    let mut control = TwoHandControl::new(&MIN_PRESS, WINDOW);
+    control.update(false, false, TestInstant::ms(0));
+    control
+}
+
+#[test]
+fn short_pulse_on_both_inputs_does_not_engage() {
+    let mut control = new_min_press_control();
+
+    assert_eq!(event(&mut control, true, true, 100), None);
+    assert_eq!(event(&mut control, false, false, 102), None);
+    assert!(!control.is_engaged());
+}
+
+#[test]
+fn deferred_presses_are_serviced_and_engage() {
+    let mut control = new_min_press_control();
+
+    let result = control.update(true, true, TestInstant::ms(100));
+    assert_eq!(result.event, None);
+    assert_eq!(result.next_service, ServiceTiming::Delay(TestDuration(20)));
+
+    assert_eq!(event(&mut control, true, true, 120), ENGAGED);
+}
+
+// --- Hold-to-confirm ---
+
+#[test]
+fn confirm_config_engages_on_press() {
+    static CONFIRM: butt_head::Config<TestDuration> = butt_head::Config {
+        confirm_duration: Some(TestDuration(3000)),
+        ..CONFIG
+    };
+    let mut control = TwoHandControl::new(&CONFIRM, WINDOW);
+    control.update(false, false, TestInstant::ms(0));
+
+    assert_eq!(event(&mut control, true, false, 100), None);
+    assert_eq!(event(&mut control, true, true, 300), ENGAGED);
+    // Reaching `Confirmed` keeps the output engaged.
+    assert_eq!(event(&mut control, true, true, 3400), None);
+    assert!(control.is_engaged());
+    assert_eq!(event(&mut control, false, true, 3500), DISENGAGED);
+}
@@ -0,0 +1,84 @@
+mod common;
+
+use butt_head::{ButtHead, Event, ServiceTiming};
+use common::{CONFIG, TestDuration, TestInstant, new_button};
+
+// --- resume_from_wake ---
+
+#[test]
+fn resume_from_wake_reports_pressed_at_wake_instant() {
+    let button = ButtHead::resume_from_wake(&CONFIG, TestInstant::ms(100));
+
+    assert!(button.is_pressed());
+    assert_eq!(button.press_instant(), Some(TestInstant::ms(100)));
+}
+
+#[test]
+fn first_update_emits_press_at_wake_instant() {
+    let mut button = ButtHead::resume_from_wake(&CONFIG, TestInstant::ms(100));
+
+    let result = button.update(true, TestInstant::ms(112));
+
+    assert_eq!(
+        result.event,
+        Some(Event::Press {
+            at: TestInstant::ms(100)
+        })
+    );
+    assert_eq!(result.next_service, ServiceTiming::Immediate);
+}
+
+#[test]
+fn hold_is_timed_from_wake_instant() {
+    let mut button = ButtHead::resume_from_wake(&CONFIG, TestInstant::ms(100));
+    button.update(true, TestInstant::ms(112)); // Press
+
+    let result = button.update(true, TestInstant::ms(113));
+    assert_eq!(result.next_service, ServiceTiming::Delay(TestDuration(487)));
+
+    let result = button.update(true, TestInstant::ms(600));
+    assert_eq!(
+        result.event,
+        Some(Event::Hold {
+            clicks_before: 0,
+            level: 0
+        })
+    );
+}
+
+#[test]
+fn short_press_released_during_wake_emits_click() {
+    let mut button = ButtHead::resume_from_wake(&CONFIG, TestInstant::ms(100));
+
+    // The press was already over by the time firmware got to run.
+    let result = button.update(false, TestInstant::ms(150));
+    assert_eq!(
+        result.event,
+        Some(Event::Press {
+            at: TestInstant::ms(100)
+        })
+    );
+
+    let result = button.update(false, TestInstant::ms(151));
+    assert_eq!(
+        result.event,
+        Some(Event::Release {
+            duration: TestDuration(50),
+            click_follows: true,
+        })
+    );
+
+    let result = button.update(false, TestInstant::ms(450));
+    assert_eq!(result.event, Some(Event::Click { count: 1 }));
+}
+
+#[test]
+fn plain_new_misses_press_released_before_first_update() {
+    // Without resume_from_wake, the same wake-up loses the click entirely.
+    let mut button = new_button();
+
+    let result = button.update(false, TestInstant::ms(150));
+
+    assert_eq!(result.event, None);
+    assert_eq!(result.next_service, ServiceTiming::Idle);
+}